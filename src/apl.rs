@@ -0,0 +1,916 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+/// APL object datasource corresponding to the [APL data source spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-data-source.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DataSource {
+    #[serde(rename = "type")]
+    ds_type: String,
+    properties: Map<String, Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    transformers: Vec<Transformer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Transformer {
+    #[serde(rename = "inputPath")]
+    input_path: String,
+    #[serde(rename = "outputName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_name: Option<String>,
+    transformer: String,
+}
+
+impl Transformer {
+    /// Constructs a transformer writing the transformed `input_path`
+    /// property to `output_name`
+    pub fn new(input_path: &str, output_name: &str, transformer: TransformerType) -> Transformer {
+        Transformer {
+            input_path: String::from(input_path),
+            output_name: Some(String::from(output_name)),
+            transformer: transformer.to_string(),
+        }
+    }
+}
+
+/// Transformers that can be applied to datasource properties
+pub enum TransformerType {
+    SsmlToSpeech,
+    SsmlToText,
+    TextToSpeech,
+    TextToHint,
+}
+
+impl fmt::Display for TransformerType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            TransformerType::SsmlToSpeech => "ssmlToSpeech",
+            TransformerType::SsmlToText => "ssmlToText",
+            TransformerType::TextToSpeech => "textToSpeech",
+            TransformerType::TextToHint => "textToHint",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// How SpeakItem highlights the text being spoken
+pub enum HighlightMode {
+    Block,
+    Line,
+}
+
+impl fmt::Display for HighlightMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            HighlightMode::Block => "block",
+            HighlightMode::Line => "line",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl DataSource {
+    /// Constructs an empty object datasource
+    pub fn new() -> DataSource {
+        DataSource::default()
+    }
+
+    /// sets a property of the datasource
+    pub fn property(mut self, key: &str, value: Value) -> Self {
+        self.properties.insert(String::from(key), value);
+        self
+    }
+
+    /// adds a transformer writing the transformed `input_path` property to
+    /// `output_name`
+    pub fn transformer(
+        mut self,
+        input_path: &str,
+        output_name: &str,
+        transformer: TransformerType,
+    ) -> Self {
+        self.transformers
+            .push(Transformer::new(input_path, output_name, transformer));
+        self
+    }
+
+    /// sets an SSML property and wires up the transformers needed for
+    /// karaoke-style highlighting: the speech is available under
+    /// `speech_output(key)` and the display text under `text_output(key)`
+    pub fn speech(self, key: &str, ssml: &str) -> Self {
+        self.property(key, Value::from(ssml))
+            .transformer(key, &speech_output(key), TransformerType::SsmlToSpeech)
+            .transformer(key, &text_output(key), TransformerType::SsmlToText)
+    }
+}
+
+impl Default for DataSource {
+    fn default() -> Self {
+        DataSource {
+            ds_type: String::from("object"),
+            properties: Map::new(),
+            transformers: Vec::new(),
+        }
+    }
+}
+
+/// Alexa.Presentation.APL.RenderDocument directive corresponding to the [APL interface spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-render-document-skill-directive.html),
+/// also used for Alexa.Presentation.APLA.RenderDocument with an APLA document
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RenderDocument {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    document: Value,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    datasources: HashMap<String, Value>,
+}
+
+impl RenderDocument {
+    /// Constructs the directive from a raw JSON document, e.g. one exported
+    /// from the authoring tool
+    pub fn new(document: Value) -> RenderDocument {
+        RenderDocument {
+            token: None,
+            document,
+            datasources: HashMap::new(),
+        }
+    }
+
+    /// Constructs the directive from a typed document
+    pub fn from_document(document: Document) -> RenderDocument {
+        RenderDocument::new(serde_json::to_value(document).unwrap_or(Value::Null))
+    }
+
+    /// Constructs the directive rendering a document saved in the developer
+    /// console, e.g. "doc://alexa/apl/documents/planets"
+    pub fn link(src: &str) -> RenderDocument {
+        RenderDocument::new(serde_json::json!({"type": "Link", "src": src}))
+    }
+
+    /// sets the token identifying the document in commands and visual context
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(String::from(token));
+        self
+    }
+
+    /// adds a named object datasource
+    pub fn datasource(self, name: &str, datasource: DataSource) -> Self {
+        let v = serde_json::to_value(datasource).unwrap_or(Value::Null);
+        self.raw_datasource(name, v)
+    }
+
+    /// adds a named datasource of any other type
+    pub fn raw_datasource(mut self, name: &str, datasource: Value) -> Self {
+        self.datasources.insert(String::from(name), datasource);
+        self
+    }
+}
+
+/// Alexa.Presentation.APLT.RenderDocument directive corresponding to the [APLT interface spec](https://developer.amazon.com/docs/alexa-presentation-language/aplt-interface.html),
+/// rendering text on character displays such as the Echo Dot with clock
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApltRenderDocument {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(rename = "targetProfile")]
+    target_profile: TargetProfile,
+    document: Value,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    datasources: HashMap<String, Value>,
+}
+
+/// Character display the APLT document is designed for
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TargetProfile {
+    /// four 7-segment characters, as on the Echo Dot with clock
+    #[serde(rename = "FOUR_CHARACTER_CLOCK")]
+    FourCharacterClock,
+    /// no particular display
+    #[serde(rename = "NONE")]
+    None,
+}
+
+impl ApltRenderDocument {
+    /// Constructs the directive from a raw JSON document
+    pub fn new(target_profile: TargetProfile, document: Value) -> ApltRenderDocument {
+        ApltRenderDocument {
+            token: None,
+            target_profile,
+            document,
+            datasources: HashMap::new(),
+        }
+    }
+
+    /// Constructs the directive from a typed document
+    pub fn from_document(target_profile: TargetProfile, document: Document) -> ApltRenderDocument {
+        let document = serde_json::to_value(document).unwrap_or(Value::Null);
+        ApltRenderDocument::new(target_profile, document)
+    }
+
+    /// sets the token identifying the document in commands
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(String::from(token));
+        self
+    }
+
+    /// adds a named datasource
+    pub fn datasource(mut self, name: &str, datasource: Value) -> Self {
+        self.datasources.insert(String::from(name), datasource);
+        self
+    }
+}
+
+/// Alexa.Presentation.APL.ExecuteCommands directive corresponding to the [APL interface spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-execute-command-directive.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ExecuteCommands {
+    token: String,
+    commands: Vec<Command>,
+}
+
+impl ExecuteCommands {
+    /// Constructs the directive running the commands on the document
+    /// rendered with the given token
+    pub fn new(token: &str, commands: Vec<Command>) -> ExecuteCommands {
+        ExecuteCommands {
+            token: String::from(token),
+            commands,
+        }
+    }
+}
+
+/// APL commands, see the [command reference](https://developer.amazon.com/docs/alexa-presentation-language/apl-standard-commands.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum Command {
+    SpeakItem {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        #[serde(rename = "highlightMode")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        highlight_mode: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        align: Option<String>,
+    },
+    SetPage {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        /// "absolute" or "relative"
+        position: String,
+        value: i32,
+    },
+    AutoPage {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        count: Option<u32>,
+        /// milliseconds to wait on each page
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+    Sequential {
+        commands: Vec<Command>,
+        #[serde(rename = "repeatCount")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        repeat_count: Option<u32>,
+    },
+    Parallel {
+        commands: Vec<Command>,
+    },
+    AnimateItem {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        /// milliseconds
+        duration: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        easing: Option<String>,
+        #[serde(rename = "repeatCount")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        repeat_count: Option<u32>,
+        /// the animated properties, e.g. opacity or transform
+        value: Vec<Value>,
+    },
+    SetValue {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        property: String,
+        value: Value,
+    },
+    SendEvent {
+        arguments: Vec<Value>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        #[serde(default)]
+        components: Vec<String>,
+    },
+    Idle {
+        /// milliseconds
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delay: Option<u32>,
+    },
+    Scroll {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        /// number of pages, negative to scroll back
+        distance: f64,
+    },
+    ScrollToIndex {
+        #[serde(rename = "componentId")]
+        component_id: String,
+        index: i32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        align: Option<String>,
+    },
+    /// any command not modeled by this crate, kept as raw JSON
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl Command {
+    /// Constructs a SpeakItem command reading the component aloud
+    pub fn speak_item(component_id: &str, highlight: HighlightMode) -> Command {
+        Command::SpeakItem {
+            component_id: String::from(component_id),
+            highlight_mode: Some(highlight.to_string()),
+            align: None,
+        }
+    }
+
+    /// Constructs a SetPage command moving a pager to the given page
+    pub fn set_page(component_id: &str, page: i32) -> Command {
+        Command::SetPage {
+            component_id: String::from(component_id),
+            position: String::from("absolute"),
+            value: page,
+        }
+    }
+
+    /// Constructs an AutoPage command advancing through all pages of a pager
+    pub fn auto_page(component_id: &str, duration: u32) -> Command {
+        Command::AutoPage {
+            component_id: String::from(component_id),
+            count: None,
+            duration: Some(duration),
+        }
+    }
+
+    /// Constructs a Sequential command running the commands in order
+    pub fn sequential(commands: Vec<Command>) -> Command {
+        Command::Sequential {
+            commands,
+            repeat_count: None,
+        }
+    }
+
+    /// Constructs a Parallel command running the commands at once
+    pub fn parallel(commands: Vec<Command>) -> Command {
+        Command::Parallel { commands }
+    }
+
+    /// Constructs a SetValue command changing a component property
+    pub fn set_value(component_id: &str, property: &str, value: Value) -> Command {
+        Command::SetValue {
+            component_id: String::from(component_id),
+            property: String::from(property),
+            value,
+        }
+    }
+
+    /// Constructs a SendEvent command sending a UserEvent request to the
+    /// skill
+    pub fn send_event(arguments: Vec<Value>) -> Command {
+        Command::SendEvent {
+            arguments,
+            components: Vec::new(),
+        }
+    }
+
+    /// Constructs an Idle command waiting for the given milliseconds
+    pub fn idle(delay: u32) -> Command {
+        Command::Idle { delay: Some(delay) }
+    }
+
+    /// Constructs a Scroll command scrolling by the given number of pages
+    pub fn scroll(component_id: &str, distance: f64) -> Command {
+        Command::Scroll {
+            component_id: String::from(component_id),
+            distance,
+        }
+    }
+
+    /// Constructs a ScrollToIndex command bringing the child at `index` into
+    /// view
+    pub fn scroll_to_index(component_id: &str, index: i32) -> Command {
+        Command::ScrollToIndex {
+            component_id: String::from(component_id),
+            index,
+            align: None,
+        }
+    }
+}
+
+/// Alexa.Presentation.APL.SendIndexListData directive corresponding to the [dynamic index list spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-data-source.html#dynamicindexlist),
+/// answering a LoadIndexListData request with a page of items
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SendIndexListData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(rename = "correlationToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_token: Option<String>,
+    #[serde(rename = "listId")]
+    list_id: String,
+    #[serde(rename = "listVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_version: Option<u32>,
+    #[serde(rename = "startIndex")]
+    start_index: i64,
+    #[serde(rename = "minimumInclusiveIndex")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_inclusive_index: Option<i64>,
+    #[serde(rename = "maximumExclusiveIndex")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maximum_exclusive_index: Option<i64>,
+    items: Vec<Value>,
+}
+
+impl SendIndexListData {
+    /// Constructs the directive sending `items` starting at `start_index`
+    pub fn new(list_id: &str, start_index: i64, items: Vec<Value>) -> SendIndexListData {
+        SendIndexListData {
+            token: None,
+            correlation_token: None,
+            list_id: String::from(list_id),
+            list_version: None,
+            start_index,
+            minimum_inclusive_index: None,
+            maximum_exclusive_index: None,
+            items,
+        }
+    }
+
+    /// sets the token of the document holding the list
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(String::from(token));
+        self
+    }
+
+    /// sets the correlation token of the LoadIndexListData request answered
+    pub fn correlation_token(mut self, correlation_token: &str) -> Self {
+        self.correlation_token = Some(String::from(correlation_token));
+        self
+    }
+
+    pub fn list_version(mut self, list_version: u32) -> Self {
+        self.list_version = Some(list_version);
+        self
+    }
+
+    /// sets the index range of the whole list, once known
+    pub fn bounds(mut self, minimum_inclusive: i64, maximum_exclusive: i64) -> Self {
+        self.minimum_inclusive_index = Some(minimum_inclusive);
+        self.maximum_exclusive_index = Some(maximum_exclusive);
+        self
+    }
+}
+
+/// Alexa.Presentation.APL.UpdateIndexListData directive, changing the items
+/// of a dynamic index list already on screen
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateIndexListData {
+    token: String,
+    #[serde(rename = "listId")]
+    list_id: String,
+    #[serde(rename = "listVersion")]
+    list_version: u32,
+    operations: Vec<ListOperation>,
+}
+
+impl UpdateIndexListData {
+    /// Constructs the directive; `list_version` must be one more than the
+    /// version of the previous update
+    pub fn new(token: &str, list_id: &str, list_version: u32) -> UpdateIndexListData {
+        UpdateIndexListData {
+            token: String::from(token),
+            list_id: String::from(list_id),
+            list_version,
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn operation(mut self, operation: ListOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+}
+
+/// Change to a dynamic index list
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum ListOperation {
+    InsertItem { index: i64, item: Value },
+    InsertMultipleItems { index: i64, items: Vec<Value> },
+    SetItem { index: i64, item: Value },
+    DeleteItem { index: i64 },
+    DeleteMultipleItems { index: i64, count: u32 },
+}
+
+/// Alexa.Presentation.APL.SendTokenListData directive corresponding to the [dynamic token list spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-data-source.html#dynamictokenlist),
+/// answering a LoadTokenListData request with a page of items
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SendTokenListData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(rename = "correlationToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_token: Option<String>,
+    #[serde(rename = "listId")]
+    list_id: String,
+    #[serde(rename = "pageToken")]
+    page_token: String,
+    #[serde(rename = "nextPageToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_page_token: Option<String>,
+    items: Vec<Value>,
+}
+
+impl SendTokenListData {
+    /// Constructs the directive sending the page of `items` requested with
+    /// `page_token`
+    pub fn new(list_id: &str, page_token: &str, items: Vec<Value>) -> SendTokenListData {
+        SendTokenListData {
+            token: None,
+            correlation_token: None,
+            list_id: String::from(list_id),
+            page_token: String::from(page_token),
+            next_page_token: None,
+            items,
+        }
+    }
+
+    /// sets the token of the document holding the list
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(String::from(token));
+        self
+    }
+
+    /// sets the correlation token of the LoadTokenListData request answered
+    pub fn correlation_token(mut self, correlation_token: &str) -> Self {
+        self.correlation_token = Some(String::from(correlation_token));
+        self
+    }
+
+    /// sets the token of the following page, if there is one
+    pub fn next_page_token(mut self, next_page_token: &str) -> Self {
+        self.next_page_token = Some(String::from(next_page_token));
+        self
+    }
+}
+
+/// APL version of documents constructed with `Document::new`
+pub const APL_VERSION: &str = "2023.3";
+
+/// APL for Audio version of documents constructed with `Document::apla`
+pub const APLA_VERSION: &str = "0.91";
+
+/// APLT version of documents constructed with `Document::aplt`
+pub const APLT_VERSION: &str = "1.0";
+
+/// APL document corresponding to the [APL document spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-document.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Document {
+    #[serde(rename = "type")]
+    doc_type: String,
+    version: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    import: Vec<Import>,
+    #[serde(rename = "mainTemplate")]
+    main_template: Value,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+/// Package imported by a document, e.g. "alexa-layouts"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Import {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+impl Document {
+    /// Constructs a document with the given main template
+    pub fn new(main_template: Value) -> Document {
+        Document {
+            doc_type: String::from("APL"),
+            version: String::from(APL_VERSION),
+            import: Vec::new(),
+            main_template,
+            extra: Map::new(),
+        }
+    }
+
+    /// Constructs an APL for Audio document with the given main template,
+    /// e.g. a Mixer layering speech over a music bed
+    pub fn apla(main_template: Value) -> Document {
+        Document {
+            doc_type: String::from("APLA"),
+            version: String::from(APLA_VERSION),
+            ..Document::new(main_template)
+        }
+    }
+
+    /// Constructs an APLT document for character displays with the given
+    /// main template
+    pub fn aplt(main_template: Value) -> Document {
+        Document {
+            doc_type: String::from("APLT"),
+            version: String::from(APLT_VERSION),
+            ..Document::new(main_template)
+        }
+    }
+
+    /// targets another APL version
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = String::from(version);
+        self
+    }
+
+    /// imports a package by name and version
+    pub fn import(mut self, name: &str, version: &str) -> Self {
+        self.import.push(Import {
+            name: String::from(name),
+            version: String::from(version),
+            source: None,
+        });
+        self
+    }
+
+    /// imports a package hosted at the given URL
+    pub fn import_from(mut self, name: &str, version: &str, source: &str) -> Self {
+        self.import.push(Import {
+            name: String::from(name),
+            version: String::from(version),
+            source: Some(String::from(source)),
+        });
+        self
+    }
+
+    /// sets any other document property, e.g. "resources", "styles" or
+    /// "layouts"
+    pub fn set(mut self, key: &str, value: Value) -> Self {
+        self.extra.insert(String::from(key), value);
+        self
+    }
+}
+
+/// name of the transformer output holding the speech for an SSML property
+pub fn speech_output(key: &str) -> String {
+    format!("{}Speech", key)
+}
+
+/// name of the transformer output holding the plain text for an SSML property
+pub fn text_output(key: &str) -> String {
+    format!("{}Text", key)
+}
+
+/// data binding expression for a property of the named datasource, for use in
+/// a component's `speech` or `text` fields
+pub fn binding(datasource: &str, property: &str) -> String {
+    format!("${{payload.{}.properties.{}}}", datasource, property)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_transformers() {
+        let ds = DataSource::new()
+            .property("title", Value::from("Planets"))
+            .speech("fact", "<speak>Jupiter has the shortest day</speak>");
+        let v = serde_json::to_value(&ds).unwrap();
+        assert_eq!(v["type"], "object");
+        assert_eq!(v["properties"]["title"], "Planets");
+        assert_eq!(
+            v["properties"]["fact"],
+            "<speak>Jupiter has the shortest day</speak>"
+        );
+        assert_eq!(v["transformers"][0]["inputPath"], "fact");
+        assert_eq!(v["transformers"][0]["outputName"], "factSpeech");
+        assert_eq!(v["transformers"][0]["transformer"], "ssmlToSpeech");
+        assert_eq!(v["transformers"][1]["outputName"], "factText");
+        assert_eq!(v["transformers"][1]["transformer"], "ssmlToText");
+    }
+
+    #[test]
+    fn test_binding_and_speak_item() {
+        assert_eq!(
+            binding("factData", &speech_output("fact")),
+            "${payload.factData.properties.factSpeech}"
+        );
+        let c = serde_json::to_value(Command::speak_item("factText", HighlightMode::Line)).unwrap();
+        assert_eq!(c["type"], "SpeakItem");
+        assert_eq!(c["componentId"], "factText");
+        assert_eq!(c["highlightMode"], "line");
+    }
+
+    #[test]
+    fn test_render_document() {
+        let doc = Document::new(serde_json::json!({
+            "parameters": ["payload"],
+            "items": [{"type": "Text", "text": "${payload.planets.properties.title}"}]
+        }))
+        .import("alexa-layouts", "1.7.0")
+        .import_from("custom", "1.0", "https://example.com/custom.json")
+        .set("styles", serde_json::json!({}));
+        let d = RenderDocument::from_document(doc)
+            .token("planets")
+            .datasource(
+                "planets",
+                DataSource::new().property("title", Value::from("Planets")),
+            );
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["token"], "planets");
+        assert_eq!(v["document"]["type"], "APL");
+        assert_eq!(v["document"]["version"], APL_VERSION);
+        assert_eq!(v["document"]["import"][0]["name"], "alexa-layouts");
+        assert!(v["document"]["import"][0].get("source").is_none());
+        assert_eq!(
+            v["document"]["import"][1]["source"],
+            "https://example.com/custom.json"
+        );
+        assert_eq!(v["document"]["mainTemplate"]["items"][0]["type"], "Text");
+        assert_eq!(v["document"]["styles"], serde_json::json!({}));
+        assert_eq!(v["datasources"]["planets"]["type"], "object");
+        assert_eq!(
+            v["datasources"]["planets"]["properties"]["title"],
+            "Planets"
+        );
+
+        let v = serde_json::to_value(RenderDocument::link("doc://alexa/apl/documents/planets"))
+            .unwrap();
+        assert_eq!(v["document"]["type"], "Link");
+        assert!(v.get("token").is_none());
+        assert!(v.get("datasources").is_none());
+    }
+
+    #[test]
+    fn test_execute_commands() {
+        let d = ExecuteCommands::new(
+            "planets",
+            vec![
+                Command::sequential(vec![
+                    Command::speak_item("factText", HighlightMode::Block),
+                    Command::idle(500),
+                    Command::set_page("pager", 2),
+                ]),
+                Command::parallel(vec![
+                    Command::scroll_to_index("list", 3),
+                    Command::set_value("title", "text", Value::from("Mars")),
+                ]),
+                Command::AnimateItem {
+                    component_id: String::from("logo"),
+                    duration: 1000,
+                    easing: None,
+                    repeat_count: Some(2),
+                    value: vec![serde_json::json!({"property": "opacity", "to": 0})],
+                },
+                Command::send_event(vec![Value::from("done")]),
+            ],
+        );
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["token"], "planets");
+        let seq = &v["commands"][0];
+        assert_eq!(seq["type"], "Sequential");
+        assert_eq!(seq["commands"][0]["type"], "SpeakItem");
+        assert_eq!(seq["commands"][0]["componentId"], "factText");
+        assert_eq!(seq["commands"][0]["highlightMode"], "block");
+        assert_eq!(seq["commands"][1]["delay"], 500);
+        assert_eq!(seq["commands"][2]["position"], "absolute");
+        assert_eq!(seq["commands"][2]["value"], 2);
+        let par = &v["commands"][1];
+        assert_eq!(par["commands"][0]["type"], "ScrollToIndex");
+        assert_eq!(par["commands"][1]["property"], "text");
+        assert_eq!(v["commands"][2]["repeatCount"], 2);
+        assert!(v["commands"][2].get("easing").is_none());
+        assert_eq!(v["commands"][3]["arguments"][0], "done");
+        assert!(v["commands"][3].get("components").is_none());
+
+        let raw = serde_json::json!({"type": "PlayMedia", "componentId": "video"});
+        let c: Command = serde_json::from_value(raw.clone()).unwrap();
+        assert!(matches!(c, Command::Other(_)));
+        let c: Command = serde_json::from_value(
+            serde_json::json!({"type": "Scroll", "componentId": "list", "distance": -1}),
+        )
+        .unwrap();
+        assert!(matches!(c, Command::Scroll { .. }));
+    }
+
+    #[test]
+    fn test_list_data() {
+        let items = vec![serde_json::json!({"name": "Mars"})];
+        let d = SendIndexListData::new("planets", 10, items.clone())
+            .correlation_token("c1")
+            .list_version(2)
+            .bounds(0, 20);
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["listId"], "planets");
+        assert_eq!(v["correlationToken"], "c1");
+        assert_eq!(v["startIndex"], 10);
+        assert_eq!(v["minimumInclusiveIndex"], 0);
+        assert_eq!(v["maximumExclusiveIndex"], 20);
+        assert_eq!(v["items"][0]["name"], "Mars");
+        assert!(v.get("token").is_none());
+
+        let d = UpdateIndexListData::new("doc", "planets", 3)
+            .operation(ListOperation::InsertItem {
+                index: 0,
+                item: items[0].clone(),
+            })
+            .operation(ListOperation::DeleteMultipleItems { index: 4, count: 2 });
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["listVersion"], 3);
+        assert_eq!(v["operations"][0]["type"], "InsertItem");
+        assert_eq!(v["operations"][0]["item"]["name"], "Mars");
+        assert_eq!(v["operations"][1]["type"], "DeleteMultipleItems");
+        assert_eq!(v["operations"][1]["count"], 2);
+
+        let d = SendTokenListData::new("planets", "page1", items).next_page_token("page2");
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["pageToken"], "page1");
+        assert_eq!(v["nextPageToken"], "page2");
+        assert!(v.get("correlationToken").is_none());
+    }
+
+    #[test]
+    fn test_apla_document() {
+        let doc = Document::apla(serde_json::json!({
+            "parameters": ["payload"],
+            "item": {
+                "type": "Mixer",
+                "items": [
+                    {"type": "Speech", "content": "${payload.user.properties.greeting}"},
+                    {"type": "Audio", "source": "soundbank://soundlibrary/ui/gameshow/amzn_ui_sfx_gameshow_intro_01"}
+                ]
+            }
+        }));
+        let d = RenderDocument::from_document(doc).raw_datasource(
+            "user",
+            serde_json::json!({"properties": {"greeting": "welcome back"}}),
+        );
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["document"]["type"], "APLA");
+        assert_eq!(v["document"]["version"], APLA_VERSION);
+        assert_eq!(v["document"]["mainTemplate"]["item"]["type"], "Mixer");
+        assert_eq!(
+            v["datasources"]["user"]["properties"]["greeting"],
+            "welcome back"
+        );
+    }
+
+    #[test]
+    fn test_aplt_document() {
+        let doc = Document::aplt(serde_json::json!({
+            "parameters": ["payload"],
+            "item": {"type": "Text", "text": "${payload.clock.properties.time}"}
+        }));
+        let d = ApltRenderDocument::from_document(TargetProfile::FourCharacterClock, doc)
+            .token("clock")
+            .datasource("clock", serde_json::json!({"properties": {"time": "1200"}}));
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["targetProfile"], "FOUR_CHARACTER_CLOCK");
+        assert_eq!(v["token"], "clock");
+        assert_eq!(v["document"]["type"], "APLT");
+        assert_eq!(v["document"]["version"], APLT_VERSION);
+        assert_eq!(v["datasources"]["clock"]["properties"]["time"], "1200");
+    }
+
+    #[test]
+    fn test_no_transformers() {
+        let v = serde_json::to_value(DataSource::new()).unwrap();
+        assert!(v.get("transformers").is_none());
+    }
+}
@@ -0,0 +1,221 @@
+//! Type-state response builder, encoding response rules in the type system
+//!
+//! A `ResponseBuilder` starts in the `NeedsSpeech` state and can only be
+//! built once speech has been added, or once a video has been launched with
+//! `launch_video` (with or without speech), which leaves out
+//! shouldEndSession as VideoApp.Launch requires. Reprompts can only be added
+//! after the session has been kept open:
+//!
+//! ```rust
+//! use alexa_sdk::builder::ResponseBuilder;
+//! use alexa_sdk::response::Speech;
+//!
+//! let _res = ResponseBuilder::new()
+//!     .speech(Speech::plain("which planet?"))
+//!     .keep_session_open()
+//!     .reprompt(Speech::plain("tell me a planet"))
+//!     .build();
+//! ```
+//!
+//! while a reprompt on a response ending the session does not compile:
+//!
+//! ```rust,compile_fail
+//! use alexa_sdk::builder::ResponseBuilder;
+//! use alexa_sdk::response::Speech;
+//!
+//! let _res = ResponseBuilder::new()
+//!     .speech(Speech::plain("goodbye"))
+//!     .reprompt(Speech::plain("still there?"))
+//!     .build();
+//! ```
+//!
+//! and a video launch cannot be combined with the session flag:
+//!
+//! ```rust,compile_fail
+//! use alexa_sdk::builder::ResponseBuilder;
+//! use alexa_sdk::response::Speech;
+//! use alexa_sdk::video::Launch;
+//!
+//! let _res = ResponseBuilder::new()
+//!     .speech(Speech::plain("here is your video"))
+//!     .launch_video(Launch::new("https://example.com/video.mp4"))
+//!     .keep_session_open()
+//!     .build();
+//! ```
+
+use super::response::{Card, Directive, Response, Speech};
+use super::video::Launch;
+use std::marker::PhantomData;
+
+/// state of a builder without output speech
+pub struct NeedsSpeech;
+/// state of a builder with speech, ending the session
+pub struct Complete;
+/// state of a builder with speech, keeping the session open
+pub struct Open;
+/// state of a builder launching a video, without shouldEndSession
+pub struct DirectiveOnly;
+
+/// Response builder tracking its state in the `S` type parameter
+pub struct ResponseBuilder<S> {
+    res: Response,
+    state: PhantomData<S>,
+}
+
+impl ResponseBuilder<NeedsSpeech> {
+    /// Constructs a builder for a response ending the session
+    pub fn new() -> ResponseBuilder<NeedsSpeech> {
+        ResponseBuilder {
+            res: Response::new(true),
+            state: PhantomData,
+        }
+    }
+
+    /// adds the output speech, making the response buildable
+    pub fn speech(self, speech: Speech) -> ResponseBuilder<Complete> {
+        self.with(|res| res.speech(speech))
+    }
+
+    /// launches a video with the VideoApp.Launch directive, leaving out
+    /// shouldEndSession as the directive requires
+    pub fn launch_video(self, launch: Launch) -> ResponseBuilder<DirectiveOnly> {
+        self.launch(launch)
+    }
+}
+
+impl Default for ResponseBuilder<NeedsSpeech> {
+    fn default() -> Self {
+        ResponseBuilder::new()
+    }
+}
+
+impl ResponseBuilder<Complete> {
+    /// launches a video after the speech with the VideoApp.Launch
+    /// directive, leaving out shouldEndSession as the directive requires
+    pub fn launch_video(self, launch: Launch) -> ResponseBuilder<DirectiveOnly> {
+        self.launch(launch)
+    }
+
+    /// keeps the session open after the response, allowing a reprompt
+    pub fn keep_session_open(self) -> ResponseBuilder<Open> {
+        self.with(|res| res.end_session(false))
+    }
+
+    /// returns the built response
+    pub fn build(self) -> Response {
+        self.res
+    }
+}
+
+impl ResponseBuilder<Open> {
+    /// adds a reprompt, spoken if the user doesn't answer
+    pub fn reprompt(self, speech: Speech) -> Self {
+        self.with(|res| res.reprompt(speech))
+    }
+
+    /// returns the built response
+    pub fn build(self) -> Response {
+        self.res
+    }
+}
+
+impl ResponseBuilder<DirectiveOnly> {
+    /// returns the built response
+    pub fn build(self) -> Response {
+        self.res
+    }
+}
+
+impl<S> ResponseBuilder<S> {
+    /// adds a card to the response
+    pub fn card(self, card: Card) -> Self {
+        self.with(|res| res.card(card))
+    }
+
+    /// adds an attribute key/value pair to the response
+    pub fn attribute(mut self, key: &str, val: &str) -> Self {
+        self.res.add_attribute(key, val);
+        self
+    }
+
+    /// adds a directive to the response
+    pub fn directive(mut self, directive: Directive) -> Self {
+        self.res.add_directive(directive);
+        self
+    }
+
+    fn launch(self, launch: Launch) -> ResponseBuilder<DirectiveOnly> {
+        self.with(|res| {
+            res.omit_end_session()
+                .directive(Directive::VideoAppLaunch(launch))
+        })
+    }
+
+    fn with<T>(self, f: impl FnOnce(Response) -> Response) -> ResponseBuilder<T> {
+        ResponseBuilder {
+            res: f(self.res),
+            state: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_session() {
+        let res = ResponseBuilder::new()
+            .card(Card::simple("Planets", "Pick a planet"))
+            .speech(Speech::plain("which planet?"))
+            .keep_session_open()
+            .reprompt(Speech::plain("tell me a planet"))
+            .attribute("step", "pick")
+            .build();
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["response"]["shouldEndSession"], false);
+        assert_eq!(
+            v["response"]["reprompt"]["outputSpeech"]["text"],
+            "tell me a planet"
+        );
+        assert_eq!(v["response"]["card"]["title"], "Planets");
+        assert_eq!(v["sessionAttributes"]["step"], "pick");
+    }
+
+    #[test]
+    fn test_complete() {
+        let res = ResponseBuilder::new()
+            .speech(Speech::plain("goodbye"))
+            .build();
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["response"]["shouldEndSession"], true);
+        assert!(v["response"].get("reprompt").is_none());
+    }
+
+    #[test]
+    fn test_launch_video() {
+        let res = ResponseBuilder::new()
+            .launch_video(Launch::new("https://example.com/video.mp4").title("Planets"))
+            .build();
+        let v = serde_json::to_value(&res).unwrap();
+        assert!(v["response"].get("shouldEndSession").is_none());
+        assert!(v["response"].get("outputSpeech").is_none());
+        assert_eq!(v["response"]["directives"][0]["type"], "VideoApp.Launch");
+        assert_eq!(
+            v["response"]["directives"][0]["videoItem"]["metadata"]["title"],
+            "Planets"
+        );
+    }
+
+    #[test]
+    fn test_launch_video_after_speech() {
+        let res = ResponseBuilder::new()
+            .speech(Speech::plain("here is your video"))
+            .launch_video(Launch::new("https://example.com/video.mp4"))
+            .build();
+        let v = serde_json::to_value(&res).unwrap();
+        assert!(v["response"].get("shouldEndSession").is_none());
+        assert_eq!(v["response"]["outputSpeech"]["text"], "here is your video");
+        assert_eq!(v["response"]["directives"][0]["type"], "VideoApp.Launch");
+    }
+}
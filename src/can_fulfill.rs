@@ -0,0 +1,111 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Answer to a CanFulfillIntentRequest, see the
+/// [name-free interaction reference](https://developer.amazon.com/docs/custom-skills/implement-canfulfillintentrequest-for-name-free-interaction.html)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CanFulfillIntent {
+    #[serde(rename = "canFulfill")]
+    can_fulfill: CanFulfill,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    slots: HashMap<String, CanFulfillSlot>,
+}
+
+/// Whether the skill can understand or fulfill an intent or slot; slots
+/// can only be fulfilled with `Yes` or `No`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CanFulfill {
+    #[serde(rename = "YES")]
+    Yes,
+    #[serde(rename = "NO")]
+    No,
+    #[serde(rename = "MAYBE")]
+    Maybe,
+}
+
+/// Per-slot answer of a `CanFulfillIntent`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CanFulfillSlot {
+    #[serde(rename = "canUnderstand")]
+    can_understand: CanFulfill,
+    #[serde(rename = "canFulfill")]
+    can_fulfill: CanFulfill,
+}
+
+impl CanFulfillIntent {
+    /// Constructs the answer for the whole intent
+    pub fn new(can_fulfill: CanFulfill) -> CanFulfillIntent {
+        CanFulfillIntent {
+            can_fulfill,
+            slots: HashMap::new(),
+        }
+    }
+
+    /// adds the answer for the named slot
+    pub fn slot(mut self, name: &str, can_understand: CanFulfill, can_fulfill: CanFulfill) -> Self {
+        self.slots.insert(
+            String::from(name),
+            CanFulfillSlot {
+                can_understand,
+                can_fulfill,
+            },
+        );
+        self
+    }
+
+    /// the answer for the whole intent
+    pub fn can_fulfill(&self) -> CanFulfill {
+        self.can_fulfill
+    }
+
+    /// the answer for the named slot, if given
+    pub fn slot_answer(&self, name: &str) -> Option<&CanFulfillSlot> {
+        self.slots.get(name)
+    }
+}
+
+impl CanFulfillSlot {
+    /// whether the skill understands the slot value
+    pub fn can_understand(&self) -> CanFulfill {
+        self.can_understand
+    }
+
+    /// whether the skill can fulfill the slot value
+    pub fn can_fulfill(&self) -> CanFulfill {
+        self.can_fulfill
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_fulfill() {
+        let c = CanFulfillIntent::new(CanFulfill::Maybe)
+            .slot("city", CanFulfill::Yes, CanFulfill::Yes)
+            .slot("date", CanFulfill::Maybe, CanFulfill::No);
+        let v = serde_json::to_value(&c).unwrap();
+        assert_eq!(v["canFulfill"], "MAYBE");
+        assert_eq!(v["slots"]["city"]["canUnderstand"], "YES");
+        assert_eq!(v["slots"]["date"]["canUnderstand"], "MAYBE");
+        assert_eq!(v["slots"]["date"]["canFulfill"], "NO");
+        let back: CanFulfillIntent = serde_json::from_value(v).unwrap();
+        assert_eq!(back, c);
+        assert_eq!(
+            back.slot_answer("city").map(|s| s.can_fulfill()),
+            Some(CanFulfill::Yes)
+        );
+
+        let v = serde_json::to_value(CanFulfillIntent::new(CanFulfill::No)).unwrap();
+        assert!(v.get("slots").is_none());
+    }
+}
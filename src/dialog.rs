@@ -0,0 +1,621 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde::de::DeserializeOwned;
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::{Map, Value};
+use super::request::{ConfirmationStatus, Intent, Request};
+use super::response::{Directive, Response, Speech};
+use std::collections::HashMap;
+
+/// Dialog.ElicitSlot directive corresponding to the [Dialog interface spec](https://developer.amazon.com/docs/custom-skills/dialog-interface-reference.html#elicitslot)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ElicitSlot {
+    #[serde(rename = "slotToElicit")]
+    slot_to_elicit: String,
+    #[serde(rename = "updatedIntent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_intent: Option<Intent>,
+}
+
+impl ElicitSlot {
+    /// Constructs a directive asking the user for the named slot
+    pub fn new(slot: &str) -> ElicitSlot {
+        ElicitSlot {
+            slot_to_elicit: String::from(slot),
+            updated_intent: None,
+        }
+    }
+
+    /// sets the intent (with any changed slot values) to continue the dialog with
+    pub fn updated_intent(mut self, intent: Intent) -> Self {
+        self.updated_intent = Some(intent);
+        self
+    }
+}
+
+/// Dialog.ConfirmSlot directive corresponding to the [Dialog interface spec](https://developer.amazon.com/docs/custom-skills/dialog-interface-reference.html#confirmslot)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConfirmSlot {
+    #[serde(rename = "slotToConfirm")]
+    slot_to_confirm: String,
+    #[serde(rename = "updatedIntent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_intent: Option<Intent>,
+}
+
+impl ConfirmSlot {
+    /// Constructs a directive asking the user to confirm the named slot
+    pub fn new(slot: &str) -> ConfirmSlot {
+        ConfirmSlot {
+            slot_to_confirm: String::from(slot),
+            updated_intent: None,
+        }
+    }
+
+    /// sets the intent (with any changed slot values) to continue the dialog with
+    pub fn updated_intent(mut self, intent: Intent) -> Self {
+        self.updated_intent = Some(intent);
+        self
+    }
+}
+
+struct SlotPrompt {
+    name: String,
+    prompt: String,
+    confirmation: Option<String>,
+}
+
+/// Result of running a `SlotCollector` over a request
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Collected<T> {
+    /// all slots are filled (and confirmed where required)
+    Complete(T),
+    /// the response to send to continue collecting slots
+    Prompt(Response),
+}
+
+/// Collects required slots without dialog rules in the interaction model:
+/// elicits each missing slot (and confirms it, if asked to) in turn, and
+/// once all are filled deserializes them into a struct whose fields are
+/// named after the slots
+pub struct SlotCollector {
+    slots: Vec<SlotPrompt>,
+}
+
+impl SlotCollector {
+    pub fn new() -> SlotCollector {
+        SlotCollector { slots: Vec::new() }
+    }
+
+    /// adds a required slot and the prompt used to elicit it
+    pub fn slot(mut self, name: &str, prompt: &str) -> Self {
+        self.slots.push(SlotPrompt {
+            name: String::from(name),
+            prompt: String::from(prompt),
+            confirmation: None,
+        });
+        self
+    }
+
+    /// adds a required slot that must also be confirmed by the user
+    /// `{slot}` placeholders in the confirmation prompt are replaced with
+    /// slot values, as in `IntentConfirmation`
+    pub fn confirmed_slot(mut self, name: &str, prompt: &str, confirmation: &str) -> Self {
+        self.slots.push(SlotPrompt {
+            name: String::from(name),
+            prompt: String::from(prompt),
+            confirmation: Some(String::from(confirmation)),
+        });
+        self
+    }
+
+    /// inspects the request's intent and either returns the collected slots
+    /// or the elicitation/confirmation response for the next missing slot
+    pub fn collect<T: DeserializeOwned>(
+        &self,
+        req: &Request,
+    ) -> Result<Collected<T>, serde_json::Error> {
+        let mut intent = match (req.body.intent.as_ref(), self.slots.first()) {
+            (Some(i), _) => i.clone(),
+            (None, Some(s)) => {
+                let d = Directive::ElicitSlot(ElicitSlot::new(&s.name));
+                return Ok(Collected::Prompt(SlotCollector::prompt(&s.prompt, d)));
+            }
+            (None, None) => {
+                return serde_json::from_value(Value::Object(Map::new())).map(Collected::Complete)
+            }
+        };
+        let mut values = Map::new();
+        for s in &self.slots {
+            let (value, status) = match intent.slots.as_ref().and_then(|h| h.get(&s.name)) {
+                Some(slot) => (
+                    String::from(slot.value().unwrap_or_default()),
+                    slot.confirmation_status(),
+                ),
+                None => (String::new(), ConfirmationStatus::None),
+            };
+            if value.is_empty() || status == ConfirmationStatus::Denied {
+                if let Some(slot) = intent.slots.as_mut().and_then(|h| h.get_mut(&s.name)) {
+                    slot.value = None;
+                    slot.confirmation_status = Some(ConfirmationStatus::None);
+                }
+                let d = ElicitSlot::new(&s.name).updated_intent(intent);
+                return Ok(Collected::Prompt(SlotCollector::prompt(
+                    &s.prompt,
+                    Directive::ElicitSlot(d),
+                )));
+            }
+            if let Some(ref c) = s.confirmation {
+                if status != ConfirmationStatus::Confirmed {
+                    let speech = fill_slots(c, &intent);
+                    let d = ConfirmSlot::new(&s.name).updated_intent(intent);
+                    return Ok(Collected::Prompt(SlotCollector::prompt(
+                        &speech,
+                        Directive::ConfirmSlot(d),
+                    )));
+                }
+            }
+            values.insert(s.name.clone(), Value::from(value));
+        }
+        serde_json::from_value(Value::Object(values)).map(Collected::Complete)
+    }
+
+    fn prompt(speech: &str, directive: Directive) -> Response {
+        let mut res = Response::new(false).speech(Speech::plain(speech));
+        res.add_directive(directive);
+        res
+    }
+}
+
+fn fill_slots(template: &str, intent: &Intent) -> String {
+    let mut speech = String::from(template);
+    if let Some(ref slots) = intent.slots {
+        for (name, slot) in slots {
+            speech = speech.replace(&format!("{{{}}}", name), slot.value().unwrap_or(""));
+        }
+    }
+    speech
+}
+
+impl Default for SlotCollector {
+    fn default() -> Self {
+        SlotCollector::new()
+    }
+}
+
+/// Dialog.ConfirmIntent directive corresponding to the [Dialog interface spec](https://developer.amazon.com/docs/custom-skills/dialog-interface-reference.html#confirmintent)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConfirmIntent {
+    #[serde(rename = "updatedIntent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_intent: Option<Intent>,
+}
+
+impl ConfirmIntent {
+    /// Constructs a directive asking the user to confirm the whole intent
+    pub fn new() -> ConfirmIntent {
+        ConfirmIntent {
+            updated_intent: None,
+        }
+    }
+
+    /// sets the intent (with any changed slot values) to be confirmed
+    pub fn updated_intent(mut self, intent: Intent) -> Self {
+        self.updated_intent = Some(intent);
+        self
+    }
+}
+
+impl Default for ConfirmIntent {
+    fn default() -> Self {
+        ConfirmIntent::new()
+    }
+}
+
+/// Dialog.DelegateRequest directive corresponding to the [Dialog interface spec](https://developer.amazon.com/docs/conversations/delegate-requests.html),
+/// handing the dialog between the skill and Alexa Conversations
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DelegateRequest {
+    target: DelegateTarget,
+    period: DelegatePeriod,
+    #[serde(rename = "updatedRequest")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_request: Option<UpdatedRequest>,
+}
+
+/// Receiver of a delegated dialog
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DelegateTarget {
+    #[serde(rename = "AMAZON.Conversations")]
+    Conversations,
+    #[serde(rename = "skill")]
+    Skill,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DelegatePeriod {
+    until: String,
+}
+
+/// Request the delegated dialog continues with
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum UpdatedRequest {
+    /// starts an Alexa Conversations dialog from the named utterance set
+    #[serde(rename = "Dialog.InputRequest")]
+    Input { input: DelegateInput },
+    /// hands the intent to the skill's intent handlers
+    #[serde(rename = "IntentRequest")]
+    Intent { intent: Intent },
+}
+
+/// Utterance set and slot values starting an Alexa Conversations dialog
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DelegateInput {
+    name: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    slots: HashMap<String, InputSlot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InputSlot {
+    name: String,
+    value: String,
+}
+
+impl DelegateRequest {
+    /// Constructs a directive handing the dialog to Alexa Conversations
+    pub fn to_conversations() -> DelegateRequest {
+        DelegateRequest::new(DelegateTarget::Conversations)
+    }
+
+    /// Constructs a directive handing the dialog back to the skill
+    pub fn to_skill() -> DelegateRequest {
+        DelegateRequest::new(DelegateTarget::Skill)
+    }
+
+    fn new(target: DelegateTarget) -> DelegateRequest {
+        DelegateRequest {
+            target,
+            period: DelegatePeriod {
+                until: String::from("EXPLICIT_RETURN"),
+            },
+            updated_request: None,
+        }
+    }
+
+    /// sets the request the receiver continues the dialog with
+    pub fn updated_request(mut self, request: UpdatedRequest) -> Self {
+        self.updated_request = Some(request);
+        self
+    }
+}
+
+impl DelegateInput {
+    /// Constructs the input for the named utterance set
+    pub fn new(name: &str) -> DelegateInput {
+        DelegateInput {
+            name: String::from(name),
+            slots: HashMap::new(),
+        }
+    }
+
+    /// adds a slot value
+    pub fn slot(mut self, name: &str, value: &str) -> Self {
+        self.slots.insert(
+            String::from(name),
+            InputSlot {
+                name: String::from(name),
+                value: String::from(value),
+            },
+        );
+        self
+    }
+}
+
+/// session attribute holding the name of the intent awaiting confirmation
+pub const PENDING_CONFIRMATION_ATTRIBUTE: &str = "pendingConfirmation";
+
+/// Outcome of an `IntentConfirmation` check
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Confirmation {
+    /// the user confirmed the intent, go ahead and execute it
+    Confirmed,
+    /// the user denied the intent, cancel it
+    Denied,
+    /// the confirmation prompt to send
+    Prompt(Response),
+    /// the request carries no intent to confirm, e.g. a LaunchRequest
+    NotApplicable,
+}
+
+/// Confirm-then-execute helper: prompts for confirmation with
+/// Dialog.ConfirmIntent (remembering the pending intent in the session
+/// attributes) and interprets the intent's confirmationStatus on the
+/// follow-up request
+pub struct IntentConfirmation {
+    prompt: String,
+}
+
+impl IntentConfirmation {
+    /// `{slot}` placeholders in the prompt are replaced with slot values
+    pub fn new(prompt: &str) -> IntentConfirmation {
+        IntentConfirmation {
+            prompt: String::from(prompt),
+        }
+    }
+
+    /// returns the name of the intent awaiting confirmation, if any
+    pub fn pending(req: &Request) -> Option<&str> {
+        req.attribute(PENDING_CONFIRMATION_ATTRIBUTE)
+    }
+
+    /// routes the request to execute, cancel, or the confirmation prompt
+    pub fn check(&self, req: &Request) -> Confirmation {
+        let intent = match req.body.intent {
+            Some(ref i) => i,
+            None => return Confirmation::NotApplicable,
+        };
+        match intent.confirmation_status() {
+            ConfirmationStatus::Confirmed => Confirmation::Confirmed,
+            ConfirmationStatus::Denied => Confirmation::Denied,
+            ConfirmationStatus::None => Confirmation::Prompt(self.prompt(intent)),
+        }
+    }
+
+    /// removes the pending intent from the response's session attributes,
+    /// to be called once a confirmed or denied intent has been handled
+    pub fn clear(res: &mut Response) {
+        res.remove_attribute(PENDING_CONFIRMATION_ATTRIBUTE);
+    }
+
+    fn prompt(&self, intent: &Intent) -> Response {
+        let speech = fill_slots(&self.prompt, intent);
+        let mut res = Response::new(false).speech(Speech::plain(&speech));
+        res.add_attribute(PENDING_CONFIRMATION_ATTRIBUTE, &intent.name);
+        res.add_directive(Directive::ConfirmIntent(
+            ConfirmIntent::new().updated_intent(intent.clone()),
+        ));
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+    struct Booking {
+        city: String,
+        date: String,
+    }
+
+    fn collector() -> SlotCollector {
+        SlotCollector::new()
+            .slot("city", "Which city?")
+            .confirmed_slot("date", "What date?", "Did you say {date}?")
+    }
+
+    fn req(city: Option<&str>, date: Option<&str>, date_status: &str) -> Request {
+        let mut v: Value = serde_json::from_str(booking_req()).unwrap();
+        if let Some(c) = city {
+            v["request"]["intent"]["slots"]["city"]["value"] = Value::from(c);
+        }
+        if let Some(d) = date {
+            v["request"]["intent"]["slots"]["date"]["value"] = Value::from(d);
+        }
+        v["request"]["intent"]["slots"]["date"]["confirmationStatus"] = Value::from(date_status);
+        serde_json::from_value(v).unwrap()
+    }
+
+    fn directive(c: Collected<Booking>) -> Value {
+        match c {
+            Collected::Prompt(r) => serde_json::to_value(&r).unwrap()["response"].clone(),
+            Collected::Complete(b) => panic!("unexpected completion {:?}", b),
+        }
+    }
+
+    #[test]
+    fn test_delegate_request() {
+        let d = DelegateRequest::to_conversations().updated_request(UpdatedRequest::Input {
+            input: DelegateInput::new("WeatherForecast").slot("cityName", "Seattle"),
+        });
+        let v = serde_json::to_value(Directive::DelegateRequest(d)).unwrap();
+        assert_eq!(v["type"], "Dialog.DelegateRequest");
+        assert_eq!(v["target"], "AMAZON.Conversations");
+        assert_eq!(v["period"]["until"], "EXPLICIT_RETURN");
+        assert_eq!(v["updatedRequest"]["type"], "Dialog.InputRequest");
+        assert_eq!(v["updatedRequest"]["input"]["name"], "WeatherForecast");
+        assert_eq!(
+            v["updatedRequest"]["input"]["slots"]["cityName"]["value"],
+            "Seattle"
+        );
+
+        let req = req(Some("Paris"), None, "NONE");
+        let intent = req.body.intent.clone().unwrap();
+        let d = DelegateRequest::to_skill().updated_request(UpdatedRequest::Intent { intent });
+        let v = serde_json::to_value(Directive::DelegateRequest(d)).unwrap();
+        assert_eq!(v["target"], "skill");
+        assert_eq!(v["updatedRequest"]["type"], "IntentRequest");
+        assert_eq!(v["updatedRequest"]["intent"]["name"], "BookTrip");
+    }
+
+    #[test]
+    fn test_elicit_first_missing() {
+        let res = directive(collector().collect(&req(None, None, "NONE")).unwrap());
+        assert_eq!(res["outputSpeech"]["text"], "Which city?");
+        assert_eq!(res["shouldEndSession"], false);
+        assert_eq!(res["directives"][0]["type"], "Dialog.ElicitSlot");
+        assert_eq!(res["directives"][0]["slotToElicit"], "city");
+        assert_eq!(res["directives"][0]["updatedIntent"]["name"], "BookTrip");
+    }
+
+    #[test]
+    fn test_confirm_slot() {
+        let res = directive(
+            collector()
+                .collect(&req(Some("Paris"), Some("2019-06-01"), "NONE"))
+                .unwrap(),
+        );
+        assert_eq!(res["outputSpeech"]["text"], "Did you say 2019-06-01?");
+        assert_eq!(res["directives"][0]["type"], "Dialog.ConfirmSlot");
+        assert_eq!(res["directives"][0]["slotToConfirm"], "date");
+    }
+
+    #[test]
+    fn test_denied_slot_is_elicited_again() {
+        let res = directive(
+            collector()
+                .collect(&req(Some("Paris"), Some("2019-06-01"), "DENIED"))
+                .unwrap(),
+        );
+        assert_eq!(res["directives"][0]["type"], "Dialog.ElicitSlot");
+        assert_eq!(res["directives"][0]["slotToElicit"], "date");
+        let slot = &res["directives"][0]["updatedIntent"]["slots"]["date"];
+        assert!(slot.get("value").is_none());
+        assert_eq!(slot["confirmationStatus"], "NONE");
+    }
+
+    #[test]
+    fn test_complete() {
+        match collector()
+            .collect::<Booking>(&req(Some("Paris"), Some("2019-06-01"), "CONFIRMED"))
+            .unwrap()
+        {
+            Collected::Complete(b) => assert_eq!(
+                b,
+                Booking {
+                    city: String::from("Paris"),
+                    date: String::from("2019-06-01")
+                }
+            ),
+            Collected::Prompt(_) => panic!("expected completion"),
+        }
+    }
+
+    #[test]
+    fn test_confirm_intent_prompt() {
+        let confirmation = IntentConfirmation::new("Book a trip to {city} on {date}?");
+        match confirmation.check(&req(Some("Paris"), Some("2019-06-01"), "CONFIRMED")) {
+            Confirmation::Prompt(r) => {
+                let v = serde_json::to_value(&r).unwrap();
+                assert_eq!(
+                    v["response"]["outputSpeech"]["text"],
+                    "Book a trip to Paris on 2019-06-01?"
+                );
+                assert_eq!(
+                    v["response"]["directives"][0]["type"],
+                    "Dialog.ConfirmIntent"
+                );
+                assert_eq!(
+                    v["response"]["directives"][0]["updatedIntent"]["name"],
+                    "BookTrip"
+                );
+                assert_eq!(v["sessionAttributes"]["pendingConfirmation"], "BookTrip");
+            }
+            c => panic!("expected prompt, got {:?}", c),
+        }
+    }
+
+    #[test]
+    fn test_confirm_intent_outcome() {
+        let confirmation = IntentConfirmation::new("Book it?");
+        let mut v: Value = serde_json::from_str(booking_req()).unwrap();
+        v["session"]["attributes"] = serde_json::json!({ "pendingConfirmation": "BookTrip" });
+        v["request"]["intent"]["confirmationStatus"] = Value::from("CONFIRMED");
+        let r: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(IntentConfirmation::pending(&r), Some("BookTrip"));
+        match confirmation.check(&r) {
+            Confirmation::Confirmed => (),
+            c => panic!("expected confirmation, got {:?}", c),
+        }
+
+        v["request"]["intent"]["confirmationStatus"] = Value::from("DENIED");
+        let r: Request = serde_json::from_value(v.clone()).unwrap();
+        match confirmation.check(&r) {
+            Confirmation::Denied => (),
+            c => panic!("expected denial, got {:?}", c),
+        }
+
+        v["request"]["type"] = Value::from("LaunchRequest");
+        v["request"].as_object_mut().unwrap().remove("intent");
+        let r: Request = serde_json::from_value(v).unwrap();
+        match confirmation.check(&r) {
+            Confirmation::NotApplicable => (),
+            c => panic!("expected no confirmation, got {:?}", c),
+        }
+    }
+
+    #[test]
+    fn test_clear_pending_confirmation() {
+        let confirmation = IntentConfirmation::new("Book it?");
+        match confirmation.check(&req(Some("Paris"), Some("2019-06-01"), "CONFIRMED")) {
+            Confirmation::Prompt(mut r) => {
+                IntentConfirmation::clear(&mut r);
+                let v = serde_json::to_value(&r).unwrap();
+                assert!(v["sessionAttributes"].get("pendingConfirmation").is_none());
+            }
+            c => panic!("expected prompt, got {:?}", c),
+        }
+    }
+
+    fn booking_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"session": {
+		"new": false,
+		"sessionId": "amzn1.echo-api.session.abc123",
+		"application": {
+			"applicationId": "amzn1.ask.skill.myappid"
+		},
+		"user": {
+			"userId": "amzn1.ask.account.theuserid"
+		}
+	},
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"apiEndpoint": "https://api.amazonalexa.com"
+		}
+	},
+	"request": {
+		"type": "IntentRequest",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-05-01T00:00:00Z",
+		"locale": "en-US",
+		"dialogState": "IN_PROGRESS",
+		"intent": {
+			"name": "BookTrip",
+			"confirmationStatus": "NONE",
+			"slots": {
+				"city": {
+					"name": "city",
+					"confirmationStatus": "NONE"
+				},
+				"date": {
+					"name": "date",
+					"confirmationStatus": "NONE"
+				}
+			}
+		}
+	}
+}"#
+    }
+}
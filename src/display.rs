@@ -0,0 +1,408 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Display.RenderTemplate directive corresponding to the [Display interface spec](https://developer.amazon.com/docs/custom-skills/display-interface-reference.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RenderTemplate {
+    template: Template,
+}
+
+impl RenderTemplate {
+    pub fn new(template: Template) -> RenderTemplate {
+        RenderTemplate { template }
+    }
+}
+
+/// Display template types
+pub enum TemplateType {
+    /// title, background image and text
+    BodyTemplate1,
+    /// image on the side of the text
+    BodyTemplate2,
+    /// image on the left of the text
+    BodyTemplate3,
+    /// text over a full screen background image
+    BodyTemplate6,
+    /// scalable foreground image
+    BodyTemplate7,
+    /// vertical list of items
+    ListTemplate1,
+    /// horizontal list of image items
+    ListTemplate2,
+}
+
+impl fmt::Display for TemplateType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            TemplateType::BodyTemplate1 => "BodyTemplate1",
+            TemplateType::BodyTemplate2 => "BodyTemplate2",
+            TemplateType::BodyTemplate3 => "BodyTemplate3",
+            TemplateType::BodyTemplate6 => "BodyTemplate6",
+            TemplateType::BodyTemplate7 => "BodyTemplate7",
+            TemplateType::ListTemplate1 => "ListTemplate1",
+            TemplateType::ListTemplate2 => "ListTemplate2",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Visibility of the back button of a template
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BackButton {
+    #[serde(rename = "VISIBLE")]
+    Visible,
+    #[serde(rename = "HIDDEN")]
+    Hidden,
+}
+
+/// A body or list template; which fields are shown depends on the template
+/// type
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Template {
+    #[serde(rename = "type")]
+    template_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(rename = "backButton")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    back_button: Option<BackButton>,
+    #[serde(rename = "backgroundImage")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_image: Option<Image>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<Image>,
+    #[serde(rename = "textContent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text_content: Option<TextContent>,
+    #[serde(rename = "listItems")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_items: Option<Vec<ListItem>>,
+}
+
+impl Template {
+    /// Constructs an empty template of the given type
+    pub fn new(template_type: TemplateType) -> Template {
+        Template {
+            template_type: template_type.to_string(),
+            token: None,
+            back_button: None,
+            background_image: None,
+            title: None,
+            image: None,
+            text_content: None,
+            list_items: None,
+        }
+    }
+
+    /// sets the token sent back in Display.ElementSelected requests
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(String::from(token));
+        self
+    }
+
+    pub fn back_button(mut self, back_button: BackButton) -> Self {
+        self.back_button = Some(back_button);
+        self
+    }
+
+    pub fn background_image(mut self, image: Image) -> Self {
+        self.background_image = Some(image);
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(String::from(title));
+        self
+    }
+
+    pub fn image(mut self, image: Image) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    pub fn text_content(mut self, text_content: TextContent) -> Self {
+        self.text_content = Some(text_content);
+        self
+    }
+
+    /// adds an item to a list template
+    pub fn list_item(mut self, item: ListItem) -> Self {
+        self.list_items.get_or_insert_with(Vec::new).push(item);
+        self
+    }
+}
+
+/// Up to three lines of text of a template or list item
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextContent {
+    #[serde(rename = "primaryText")]
+    primary_text: Text,
+    #[serde(rename = "secondaryText")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secondary_text: Option<Text>,
+    #[serde(rename = "tertiaryText")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tertiary_text: Option<Text>,
+}
+
+impl TextContent {
+    pub fn new(primary_text: Text) -> TextContent {
+        TextContent {
+            primary_text,
+            secondary_text: None,
+            tertiary_text: None,
+        }
+    }
+
+    pub fn secondary(mut self, text: Text) -> Self {
+        self.secondary_text = Some(text);
+        self
+    }
+
+    pub fn tertiary(mut self, text: Text) -> Self {
+        self.tertiary_text = Some(text);
+        self
+    }
+}
+
+/// Selectable item of a list template
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ListItem {
+    token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<Image>,
+    #[serde(rename = "textContent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text_content: Option<TextContent>,
+}
+
+impl ListItem {
+    /// Constructs an item with the token sent back when it is selected
+    pub fn new(token: &str) -> ListItem {
+        ListItem {
+            token: String::from(token),
+            image: None,
+            text_content: None,
+        }
+    }
+
+    pub fn image(mut self, image: Image) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    pub fn text_content(mut self, text_content: TextContent) -> Self {
+        self.text_content = Some(text_content);
+        self
+    }
+}
+
+/// Template image with one or more sources for different screen sizes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Image {
+    #[serde(rename = "contentDescription")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_description: Option<String>,
+    sources: Vec<ImageSource>,
+}
+
+impl Image {
+    /// Constructs an image with a single source
+    pub fn new(url: &str) -> Image {
+        Image {
+            content_description: None,
+            sources: vec![ImageSource::new(url)],
+        }
+    }
+
+    /// sets the description read by screen readers
+    pub fn description(mut self, description: &str) -> Self {
+        self.content_description = Some(String::from(description));
+        self
+    }
+
+    /// adds a source for another screen size
+    pub fn source(mut self, source: ImageSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ImageSource {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<String>,
+    #[serde(rename = "widthPixels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width_pixels: Option<u32>,
+    #[serde(rename = "heightPixels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height_pixels: Option<u32>,
+}
+
+impl ImageSource {
+    pub fn new(url: &str) -> ImageSource {
+        ImageSource {
+            url: String::from(url),
+            size: None,
+            width_pixels: None,
+            height_pixels: None,
+        }
+    }
+
+    /// sets the size class, e.g. "X_SMALL" or "LARGE"
+    pub fn size(mut self, size: &str) -> Self {
+        self.size = Some(String::from(size));
+        self
+    }
+
+    pub fn dimensions(mut self, width: u32, height: u32) -> Self {
+        self.width_pixels = Some(width);
+        self.height_pixels = Some(height);
+        self
+    }
+}
+
+/// Hint directive corresponding to the [Display interface spec](https://developer.amazon.com/docs/custom-skills/display-interface-reference.html#hint-directive),
+/// shown as a "Try: ..." footer on screen devices
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Hint {
+    hint: Text,
+}
+
+impl Hint {
+    /// Constructs a hint with the given plain text, e.g. "ask for a planet"
+    pub fn new(text: &str) -> Hint {
+        Hint {
+            hint: Text::plain(text),
+        }
+    }
+}
+
+/// Text format of a `Text` field
+pub enum TextType {
+    Plain,
+    Rich,
+}
+
+impl fmt::Display for TextType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            TextType::Plain => "PlainText",
+            TextType::Rich => "RichText",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Plain or rich (markup) text shown on a display
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Text {
+    #[serde(rename = "type")]
+    text_type: String,
+    text: String,
+}
+
+impl Text {
+    /// Constructs plain text
+    pub fn plain(text: &str) -> Text {
+        Text {
+            text_type: TextType::Plain.to_string(),
+            text: String::from(text),
+        }
+    }
+
+    /// Constructs rich text, which may contain markup such as `<b>`
+    pub fn rich(text: &str) -> Text {
+        Text {
+            text_type: TextType::Rich.to_string(),
+            text: String::from(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hint() {
+        let v = serde_json::to_value(Hint::new("ask for a planet")).unwrap();
+        assert_eq!(v["hint"]["type"], "PlainText");
+        assert_eq!(v["hint"]["text"], "ask for a planet");
+    }
+
+    #[test]
+    fn test_body_template() {
+        let t = Template::new(TemplateType::BodyTemplate2)
+            .token("planet")
+            .back_button(BackButton::Hidden)
+            .title("Jupiter")
+            .image(Image::new("https://example.com/jupiter.png").description("Jupiter"))
+            .text_content(
+                TextContent::new(Text::rich("<b>Jupiter</b>"))
+                    .secondary(Text::plain("the largest planet"))
+                    .tertiary(Text::plain("79 moons")),
+            );
+        let v = serde_json::to_value(RenderTemplate::new(t)).unwrap();
+        let t = &v["template"];
+        assert_eq!(t["type"], "BodyTemplate2");
+        assert_eq!(t["token"], "planet");
+        assert_eq!(t["backButton"], "HIDDEN");
+        assert_eq!(
+            t["image"]["sources"][0]["url"],
+            "https://example.com/jupiter.png"
+        );
+        assert_eq!(t["image"]["contentDescription"], "Jupiter");
+        assert_eq!(t["textContent"]["primaryText"]["type"], "RichText");
+        assert_eq!(
+            t["textContent"]["secondaryText"]["text"],
+            "the largest planet"
+        );
+        assert_eq!(t["textContent"]["tertiaryText"]["type"], "PlainText");
+        assert!(t.get("listItems").is_none());
+    }
+
+    #[test]
+    fn test_list_template() {
+        let t = Template::new(TemplateType::ListTemplate2)
+            .list_item(
+                ListItem::new("mars")
+                    .image(
+                        Image::new("https://example.com/mars.png").source(
+                            ImageSource::new("https://example.com/mars-large.png")
+                                .size("LARGE")
+                                .dimensions(1200, 800),
+                        ),
+                    )
+                    .text_content(TextContent::new(Text::plain("Mars"))),
+            )
+            .list_item(ListItem::new("venus"));
+        let v = serde_json::to_value(t).unwrap();
+        assert_eq!(v["type"], "ListTemplate2");
+        assert_eq!(v["listItems"][0]["token"], "mars");
+        assert_eq!(v["listItems"][0]["image"]["sources"][1]["size"], "LARGE");
+        assert_eq!(
+            v["listItems"][0]["image"]["sources"][1]["widthPixels"],
+            1200
+        );
+        assert_eq!(v["listItems"][1]["token"], "venus");
+        assert!(v["listItems"][1].get("textContent").is_none());
+    }
+}
@@ -0,0 +1,219 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Dialog.UpdateDynamicEntities directive corresponding to the [dynamic entities spec](https://developer.amazon.com/docs/custom-skills/use-dynamic-entities-for-customized-interactions.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateDynamicEntities {
+    #[serde(rename = "updateBehavior")]
+    update_behavior: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<Vec<EntityType>>,
+}
+
+/// Update behavior of a dynamic entities directive
+pub enum UpdateBehavior {
+    Replace,
+    Clear,
+}
+
+impl fmt::Display for UpdateBehavior {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            UpdateBehavior::Replace => "REPLACE",
+            UpdateBehavior::Clear => "CLEAR",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl UpdateDynamicEntities {
+    /// Constructs a directive replacing the dynamic entities of the given
+    /// slot types
+    pub fn replace(types: Vec<EntityType>) -> UpdateDynamicEntities {
+        UpdateDynamicEntities {
+            update_behavior: UpdateBehavior::Replace.to_string(),
+            types: Some(types),
+        }
+    }
+
+    /// Constructs a directive clearing all dynamic entities
+    pub fn clear() -> UpdateDynamicEntities {
+        UpdateDynamicEntities {
+            update_behavior: UpdateBehavior::Clear.to_string(),
+            types: None,
+        }
+    }
+}
+
+/// Dynamic values for one slot type
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EntityType {
+    name: String,
+    values: Vec<EntityValue>,
+}
+
+impl EntityType {
+    pub fn new(name: &str, values: Vec<EntityValue>) -> EntityType {
+        EntityType {
+            name: String::from(name),
+            values,
+        }
+    }
+
+    /// Constructs the entity type from a `ToDynamicEntities` implementation
+    pub fn of<T: ToDynamicEntities>() -> EntityType {
+        EntityType::new(T::slot_type(), T::entity_values())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EntityValue {
+    id: String,
+    name: EntityName,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EntityName {
+    value: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    synonyms: Vec<String>,
+}
+
+impl EntityValue {
+    pub fn new(id: &str, value: &str, synonyms: &[&str]) -> EntityValue {
+        EntityValue {
+            id: String::from(id),
+            name: EntityName {
+                value: String::from(value),
+                synonyms: synonyms.iter().map(|s| String::from(*s)).collect(),
+            },
+        }
+    }
+}
+
+/// Types (usually enums) that provide the dynamic entity values of a slot type
+/// implemented for enums declared with `dynamic_entities!`
+pub trait ToDynamicEntities: Sized {
+    /// name of the slot type in the interaction model
+    fn slot_type() -> &'static str;
+
+    /// the entity values, one per variant
+    fn entity_values() -> Vec<EntityValue>;
+
+    /// maps a resolved entity ID back to the variant
+    fn from_entity_id(id: &str) -> Option<Self>;
+}
+
+/// Declares an enum whose variants are the dynamic entities of a slot type,
+/// implementing `ToDynamicEntities` for it. The variant name is used as both
+/// the entity ID and value, and is followed by optional synonyms.
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate alexa_sdk;
+///
+/// use alexa_sdk::entities::{EntityType, ToDynamicEntities};
+///
+/// dynamic_entities! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Drink: "DrinkType" {
+///         Espresso => ["short black"],
+///         Latte => ["milky coffee", "cafe latte"],
+///         Tea,
+///     }
+/// }
+///
+/// fn main() {
+///     let _types = EntityType::of::<Drink>();
+///     assert_eq!(Drink::from_entity_id("Latte"), Some(Drink::Latte));
+/// }
+/// ```
+#[macro_export]
+macro_rules! dynamic_entities {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident : $slot_type:literal {
+            $($variant:ident $(=> [$($synonym:expr),* $(,)?])?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::entities::ToDynamicEntities for $name {
+            fn slot_type() -> &'static str {
+                $slot_type
+            }
+
+            fn entity_values() -> Vec<$crate::entities::EntityValue> {
+                vec![$(
+                    $crate::entities::EntityValue::new(
+                        stringify!($variant),
+                        stringify!($variant),
+                        &[$($($synonym),*)?],
+                    )
+                ),*]
+            }
+
+            fn from_entity_id(id: &str) -> Option<Self> {
+                match id {
+                    $(stringify!($variant) => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    dynamic_entities! {
+        #[derive(Debug, PartialEq)]
+        enum Color: "ColorType" {
+            Red => ["crimson", "scarlet"],
+            Blue => ["navy"],
+            Green,
+        }
+    }
+
+    #[test]
+    fn test_entity_values() {
+        let values = Color::entity_values();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].id, "Red");
+        assert_eq!(values[0].name.synonyms, vec!["crimson", "scarlet"]);
+        assert!(values[2].name.synonyms.is_empty());
+        assert_eq!(Color::from_entity_id("Blue"), Some(Color::Blue));
+        assert_eq!(Color::from_entity_id("Purple"), None);
+    }
+
+    #[test]
+    fn test_replace_directive() {
+        let d = UpdateDynamicEntities::replace(vec![EntityType::of::<Color>()]);
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["updateBehavior"], "REPLACE");
+        assert_eq!(v["types"][0]["name"], "ColorType");
+        assert_eq!(v["types"][0]["values"][1]["name"]["value"], "Blue");
+        assert_eq!(v["types"][0]["values"][1]["name"]["synonyms"][0], "navy");
+        assert!(v["types"][0]["values"][2]["name"].get("synonyms").is_none());
+    }
+
+    #[test]
+    fn test_clear_directive() {
+        let v = serde_json::to_value(UpdateDynamicEntities::clear()).unwrap();
+        assert_eq!(v["updateBehavior"], "CLEAR");
+        assert!(v.get("types").is_none());
+    }
+}
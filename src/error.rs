@@ -0,0 +1,28 @@
+use std::error;
+use std::fmt;
+
+/// Error parsing a request or serializing a response
+#[derive(Debug)]
+pub enum Error {
+    /// the request JSON is malformed or doesn't match the request spec
+    Request(serde_json::Error),
+    /// the response could not be serialized
+    Response(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Request(e) => write!(f, "invalid Alexa request: {}", e),
+            Error::Response(e) => write!(f, "could not serialize Alexa response: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Request(e) | Error::Response(e) => Some(e),
+        }
+    }
+}
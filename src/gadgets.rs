@@ -0,0 +1,539 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value;
+use super::request::{InputHandlerEvent, Request};
+use super::response::Directive;
+use std::collections::HashMap;
+use std::fmt;
+
+/// GameEngine.StartInputHandler directive corresponding to the [Game Engine spec](https://developer.amazon.com/docs/gadget-skills/gameengine-interface-reference.html#start)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StartInputHandler {
+    timeout: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    proxies: Vec<String>,
+    recognizers: HashMap<String, Recognizer>,
+    events: HashMap<String, EventReport>,
+}
+
+impl StartInputHandler {
+    /// Constructs an input handler running for `timeout` milliseconds
+    pub fn new(timeout: u64) -> StartInputHandler {
+        StartInputHandler {
+            timeout,
+            proxies: Vec::new(),
+            recognizers: HashMap::new(),
+            events: HashMap::new(),
+        }
+    }
+
+    /// adds a proxy name, bound to the first unknown gadget that matches it
+    pub fn proxy(mut self, name: &str) -> Self {
+        self.proxies.push(String::from(name));
+        self
+    }
+
+    /// adds a named recognizer
+    pub fn recognizer(mut self, name: &str, recognizer: Recognizer) -> Self {
+        self.recognizers.insert(String::from(name), recognizer);
+        self
+    }
+
+    /// adds a named event reported to the skill
+    pub fn event(mut self, name: &str, event: EventReport) -> Self {
+        self.events.insert(String::from(name), event);
+        self
+    }
+}
+
+/// A pattern recognizer
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Recognizer {
+    #[serde(rename = "type")]
+    recognizer_type: String,
+    fuzzy: bool,
+    anchor: String,
+    pattern: Vec<Pattern>,
+}
+
+impl Recognizer {
+    /// Constructs a "match" recognizer; `anchor` is one of start, end or anywhere
+    pub fn pattern(anchor: &str, fuzzy: bool, pattern: Vec<Pattern>) -> Recognizer {
+        Recognizer {
+            recognizer_type: String::from("match"),
+            fuzzy,
+            anchor: String::from(anchor),
+            pattern,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Pattern {
+    #[serde(rename = "gadgetIds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gadget_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<String>,
+}
+
+impl Pattern {
+    /// Constructs a pattern step matching a button press on the given gadget
+    /// (or proxy)
+    pub fn button_down(gadget_id: &str) -> Pattern {
+        Pattern {
+            gadget_ids: Some(vec![String::from(gadget_id)]),
+            action: Some(String::from("down")),
+        }
+    }
+}
+
+/// An event reported to the skill when its recognizers are met
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EventReport {
+    meets: Vec<String>,
+    reports: String,
+    #[serde(rename = "shouldEndInputHandler")]
+    should_end_input_handler: bool,
+    #[serde(rename = "maximumInvocations")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maximum_invocations: Option<u32>,
+}
+
+impl EventReport {
+    /// Constructs an event; `reports` is one of history, matches or nothing
+    pub fn new(meets: &[&str], reports: &str, should_end: bool) -> EventReport {
+        EventReport {
+            meets: meets.iter().map(|m| String::from(*m)).collect(),
+            reports: String::from(reports),
+            should_end_input_handler: should_end,
+            maximum_invocations: None,
+        }
+    }
+
+    /// limits how often the event is reported
+    pub fn maximum_invocations(mut self, max: u32) -> Self {
+        self.maximum_invocations = Some(max);
+        self
+    }
+}
+
+const ROLL_CALL_COMPLETE: &str = "roll_call_complete";
+const ROLL_CALL_TIMEOUT: &str = "roll_call_timeout";
+
+/// The standard roll call recipe: asks for `buttons` Echo Buttons to be
+/// pressed in turn, reporting each check-in and the completed roll call
+pub struct RollCall {
+    buttons: usize,
+    timeout: u64,
+}
+
+/// Outcome of a roll call input handler event
+#[derive(Debug, PartialEq)]
+pub enum RollCallEvent {
+    /// the button with the given (zero based) index checked in
+    CheckedIn(usize, String),
+    /// all buttons checked in, gadget IDs in proxy order
+    Complete(Vec<String>),
+    TimedOut,
+}
+
+impl RollCall {
+    /// Constructs a roll call for the given number of buttons with a 30 second
+    /// timeout
+    pub fn new(buttons: usize) -> RollCall {
+        RollCall {
+            buttons,
+            timeout: 30000,
+        }
+    }
+
+    /// sets the timeout in milliseconds
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn proxy(i: usize) -> String {
+        format!("button_{}", i + 1)
+    }
+
+    fn checked_in_event(i: usize) -> String {
+        format!("button_{}_checked_in", i + 1)
+    }
+
+    /// Constructs the StartInputHandler directive for the roll call
+    pub fn directive(&self) -> Directive {
+        let mut h = StartInputHandler::new(self.timeout);
+        let mut all = Vec::new();
+        for i in 0..self.buttons {
+            let proxy = RollCall::proxy(i);
+            let recognizer = format!("{}_pressed", proxy);
+            all.push(Pattern::button_down(&proxy));
+            h = h
+                .proxy(&proxy)
+                .recognizer(
+                    &recognizer,
+                    Recognizer::pattern("end", true, vec![Pattern::button_down(&proxy)]),
+                )
+                .event(
+                    &RollCall::checked_in_event(i),
+                    EventReport::new(&[&recognizer], "matches", false).maximum_invocations(1),
+                );
+        }
+        h = h
+            .recognizer("all_pressed", Recognizer::pattern("start", true, all))
+            .event(
+                ROLL_CALL_COMPLETE,
+                EventReport::new(&["all_pressed"], "matches", true),
+            )
+            .event(
+                ROLL_CALL_TIMEOUT,
+                EventReport::new(&["timed out"], "history", true),
+            );
+        Directive::StartInputHandler(h)
+    }
+
+    /// Interprets the input handler events of a request started by this roll
+    /// call
+    pub fn events(&self, req: &Request) -> Vec<RollCallEvent> {
+        let events = match req.input_handler_events() {
+            Some(e) => e,
+            None => return Vec::new(),
+        };
+        events.iter().filter_map(|e| self.parse_event(e)).collect()
+    }
+
+    fn parse_event(&self, event: &InputHandlerEvent) -> Option<RollCallEvent> {
+        if event.name == ROLL_CALL_TIMEOUT {
+            return Some(RollCallEvent::TimedOut);
+        }
+        if event.name == ROLL_CALL_COMPLETE {
+            let mut ids: Vec<String> = Vec::new();
+            for ie in event.input_events.iter().filter(|ie| ie.action == "down") {
+                if !ids.contains(&ie.gadget_id) {
+                    ids.push(ie.gadget_id.clone());
+                }
+            }
+            return Some(RollCallEvent::Complete(ids));
+        }
+        let i = (0..self.buttons).find(|i| event.name == RollCall::checked_in_event(*i))?;
+        let gadget = event
+            .input_events
+            .iter()
+            .rev()
+            .find(|ie| ie.action == "down")?;
+        Some(RollCallEvent::CheckedIn(i, gadget.gadget_id.clone()))
+    }
+}
+
+/// CustomInterfaceController.StartEventHandler directive corresponding to the [Custom Interface spec](https://developer.amazon.com/docs/alexa-gadgets-toolkit/receive-custom-event-from-gadget.html#start)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StartEventHandler {
+    token: String,
+    #[serde(rename = "eventFilter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_filter: Option<EventFilter>,
+    expiration: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EventFilter {
+    #[serde(rename = "filterExpression")]
+    filter_expression: Value,
+    #[serde(rename = "filterMatchAction")]
+    filter_match_action: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Expiration {
+    #[serde(rename = "durationInMilliseconds")]
+    duration_in_milliseconds: u64,
+    #[serde(rename = "expirationPayload")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expiration_payload: Option<Value>,
+}
+
+/// What the event handler does when an event matches its filter
+pub enum FilterMatchAction {
+    Send,
+    SendAndTerminate,
+}
+
+impl fmt::Display for FilterMatchAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            FilterMatchAction::Send => "SEND",
+            FilterMatchAction::SendAndTerminate => "SEND_AND_TERMINATE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl StartEventHandler {
+    /// Constructs an event handler identified by `token` that expires after
+    /// `duration` milliseconds
+    pub fn new(token: &str, duration: u64) -> StartEventHandler {
+        StartEventHandler {
+            token: String::from(token),
+            event_filter: None,
+            expiration: Expiration {
+                duration_in_milliseconds: duration,
+                expiration_payload: None,
+            },
+        }
+    }
+
+    /// only forwards events matching the filter expression
+    pub fn filter(mut self, filter: FilterExpression, action: FilterMatchAction) -> Self {
+        self.event_filter = Some(EventFilter {
+            filter_expression: filter.to_value(),
+            filter_match_action: action.to_string(),
+        });
+        self
+    }
+
+    /// sets the payload sent to the skill when the handler expires
+    pub fn expiration_payload(mut self, payload: Value) -> Self {
+        self.expiration.expiration_payload = Some(payload);
+        self
+    }
+}
+
+/// Event filter expression over the custom event, e.g.
+/// `FilterExpression::namespace("Custom.Robot").and(FilterExpression::name("EyesGlowing"))`
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpression {
+    Equals(String, Value),
+    And(Vec<FilterExpression>),
+    Or(Vec<FilterExpression>),
+}
+
+impl FilterExpression {
+    /// matches when the event field at `var` (e.g. "header.namespace",
+    /// "endpoint.endpointId") equals `value`
+    pub fn equals(var: &str, value: Value) -> FilterExpression {
+        FilterExpression::Equals(String::from(var), value)
+    }
+
+    /// matches events with the given header namespace
+    pub fn namespace(namespace: &str) -> FilterExpression {
+        FilterExpression::equals("header.namespace", Value::from(namespace))
+    }
+
+    /// matches events with the given header name
+    pub fn name(name: &str) -> FilterExpression {
+        FilterExpression::equals("header.name", Value::from(name))
+    }
+
+    /// matches events from the given endpoint
+    pub fn endpoint(endpoint_id: &str) -> FilterExpression {
+        FilterExpression::equals("endpoint.endpointId", Value::from(endpoint_id))
+    }
+
+    /// combines with another expression, both must match
+    pub fn and(self, other: FilterExpression) -> FilterExpression {
+        match self {
+            FilterExpression::And(mut v) => {
+                v.push(other);
+                FilterExpression::And(v)
+            }
+            e => FilterExpression::And(vec![e, other]),
+        }
+    }
+
+    /// combines with another expression, either may match
+    pub fn or(self, other: FilterExpression) -> FilterExpression {
+        match self {
+            FilterExpression::Or(mut v) => {
+                v.push(other);
+                FilterExpression::Or(v)
+            }
+            e => FilterExpression::Or(vec![e, other]),
+        }
+    }
+
+    /// renders the expression as the JSON Logic used by the event filter
+    pub fn to_value(&self) -> Value {
+        match *self {
+            FilterExpression::Equals(ref var, ref value) => {
+                serde_json::json!({ "==": [{ "var": var }, value] })
+            }
+            FilterExpression::And(ref v) => {
+                let exprs: Vec<Value> = v.iter().map(|e| e.to_value()).collect();
+                serde_json::json!({ "and": exprs })
+            }
+            FilterExpression::Or(ref v) => {
+                let exprs: Vec<Value> = v.iter().map(|e| e.to_value()).collect();
+                serde_json::json!({ "or": exprs })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_call_directive() {
+        let d = RollCall::new(2).timeout(10000).directive();
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["type"], "GameEngine.StartInputHandler");
+        assert_eq!(v["timeout"], 10000);
+        assert_eq!(v["proxies"][1], "button_2");
+        assert_eq!(
+            v["recognizers"]["all_pressed"]["pattern"][1]["gadgetIds"][0],
+            "button_2"
+        );
+        assert_eq!(
+            v["recognizers"]["all_pressed"]["pattern"][1]["action"],
+            "down"
+        );
+        assert_eq!(
+            v["events"]["button_1_checked_in"]["meets"][0],
+            "button_1_pressed"
+        );
+        assert_eq!(
+            v["events"]["roll_call_complete"]["shouldEndInputHandler"],
+            true
+        );
+        assert_eq!(v["events"]["roll_call_timeout"]["meets"][0], "timed out");
+    }
+
+    #[test]
+    fn test_roll_call_events() {
+        let req: Request = serde_json::from_str(input_handler_req()).unwrap();
+        let events = RollCall::new(2).events(&req);
+        assert_eq!(
+            events,
+            vec![
+                RollCallEvent::CheckedIn(1, String::from("amzn1.ask.gadget.B")),
+                RollCallEvent::Complete(vec![
+                    String::from("amzn1.ask.gadget.A"),
+                    String::from("amzn1.ask.gadget.B")
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_expression() {
+        let f = FilterExpression::namespace("Custom.Robot")
+            .and(FilterExpression::name("EyesGlowing"))
+            .and(FilterExpression::endpoint("gadget-1").or(FilterExpression::endpoint("gadget-2")));
+        assert_eq!(
+            f.to_value(),
+            serde_json::json!({
+                "and": [
+                    { "==": [{ "var": "header.namespace" }, "Custom.Robot"] },
+                    { "==": [{ "var": "header.name" }, "EyesGlowing"] },
+                    { "or": [
+                        { "==": [{ "var": "endpoint.endpointId" }, "gadget-1"] },
+                        { "==": [{ "var": "endpoint.endpointId" }, "gadget-2"] }
+                    ] }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_start_event_handler() {
+        let d = Directive::StartEventHandler(
+            StartEventHandler::new("session-token", 90000)
+                .filter(
+                    FilterExpression::namespace("Custom.Robot"),
+                    FilterMatchAction::SendAndTerminate,
+                )
+                .expiration_payload(serde_json::json!({ "gameOver": true })),
+        );
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["type"], "CustomInterfaceController.StartEventHandler");
+        assert_eq!(v["token"], "session-token");
+        assert_eq!(v["eventFilter"]["filterMatchAction"], "SEND_AND_TERMINATE");
+        assert_eq!(
+            v["eventFilter"]["filterExpression"]["=="][1],
+            "Custom.Robot"
+        );
+        assert_eq!(v["expiration"]["durationInMilliseconds"], 90000);
+        assert_eq!(v["expiration"]["expirationPayload"]["gameOver"], true);
+    }
+
+    fn input_handler_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"session": {
+		"new": false,
+		"sessionId": "amzn1.echo-api.session.abc123",
+		"application": {
+			"applicationId": "amzn1.ask.skill.myappid"
+		},
+		"user": {
+			"userId": "amzn1.ask.account.theuserid"
+		}
+	},
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"user": {
+				"userId": "amzn1.ask.account.theuserid"
+			},
+			"apiEndpoint": "https://api.amazonalexa.com"
+		}
+	},
+	"request": {
+		"type": "GameEngine.InputHandlerEvent",
+		"requestId": "amzn1.echo-api.request.406fbc75",
+		"timestamp": "2018-01-01T00:00:00Z",
+		"locale": "en-US",
+		"originatingRequestId": "amzn1.echo-api.request.start",
+		"events": [
+			{
+				"name": "button_2_checked_in",
+				"inputEvents": [
+					{
+						"gadgetId": "amzn1.ask.gadget.B",
+						"timestamp": "2018-01-01T00:00:01Z",
+						"color": "000000",
+						"feature": "press",
+						"action": "down"
+					}
+				]
+			},
+			{
+				"name": "roll_call_complete",
+				"inputEvents": [
+					{
+						"gadgetId": "amzn1.ask.gadget.A",
+						"timestamp": "2018-01-01T00:00:00Z",
+						"color": "000000",
+						"feature": "press",
+						"action": "down"
+					},
+					{
+						"gadgetId": "amzn1.ask.gadget.B",
+						"timestamp": "2018-01-01T00:00:01Z",
+						"color": "000000",
+						"feature": "press",
+						"action": "down"
+					}
+				]
+			}
+		]
+	}
+}"#
+    }
+}
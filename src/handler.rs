@@ -0,0 +1,162 @@
+use super::presentation::ViewportProfile;
+use super::request::{IntentType, Locale, Request, Slot};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+
+/// Input passed to request handlers, wrapping the request and caching values
+/// derived from it the first time they are asked for
+pub struct HandlerInput<'a> {
+    request: &'a Request,
+    locale: OnceCell<Locale>,
+    intent: OnceCell<IntentType>,
+    slots: OnceCell<HashMap<String, String>>,
+    viewport_profile: OnceCell<ViewportProfile>,
+}
+
+impl<'a> HandlerInput<'a> {
+    /// Constructs the handler input for a request
+    pub fn new(request: &'a Request) -> HandlerInput<'a> {
+        HandlerInput {
+            request,
+            locale: OnceCell::new(),
+            intent: OnceCell::new(),
+            slots: OnceCell::new(),
+            viewport_profile: OnceCell::new(),
+        }
+    }
+
+    /// the underlying request
+    pub fn request(&self) -> &'a Request {
+        self.request
+    }
+
+    /// the locale of the request
+    pub fn locale(&self) -> &Locale {
+        self.locale.get_or_init(|| self.request.locale())
+    }
+
+    /// the intent of the request
+    pub fn intent(&self) -> &IntentType {
+        self.intent.get_or_init(|| self.request.intent())
+    }
+
+    /// the viewport profile of the requesting device
+    pub fn viewport_profile(&self) -> &ViewportProfile {
+        self.viewport_profile
+            .get_or_init(|| self.request.viewport_profile())
+    }
+
+    /// the filled slots of the intent, keyed by slot name; values are the
+    /// entity resolution names where resolution succeeded, the spoken values
+    /// otherwise
+    pub fn slots(&self) -> &HashMap<String, String> {
+        self.slots.get_or_init(|| {
+            let slots = self
+                .request
+                .body
+                .intent
+                .as_ref()
+                .and_then(|i| i.slots.as_ref());
+            match slots {
+                Some(slots) => slots
+                    .iter()
+                    .filter_map(|(k, s)| Some((k.clone(), resolved_value(s)?)))
+                    .collect(),
+                None => HashMap::new(),
+            }
+        })
+    }
+
+    /// the (resolved) value of the named slot, if filled
+    pub fn slot(&self, name: &str) -> Option<&str> {
+        self.slots().get(name).map(|s| s.as_str())
+    }
+}
+
+fn resolved_value(slot: &Slot) -> Option<String> {
+    match slot.resolved_name() {
+        Some(name) => Some(String::from(name)),
+        None => slot.value().map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handler_input() {
+        let req: Request = serde_json::from_str(resolved_req()).unwrap();
+        let input = HandlerInput::new(&req);
+        assert_eq!(*input.locale(), Locale::BritishEnglish);
+        assert_eq!(
+            *input.intent(),
+            IntentType::User(String::from("OrderIntent"))
+        );
+        assert_eq!(input.slot("drink"), Some("Latte"));
+        assert_eq!(input.slot("size"), Some("grande"));
+        assert_eq!(input.slot("milk"), None);
+        assert_eq!(input.slots().len(), 2);
+        assert_eq!(*input.viewport_profile(), ViewportProfile::Unknown);
+    }
+
+    fn resolved_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			}
+		}
+	},
+	"request": {
+		"type": "IntentRequest",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-03-01T10:00:00Z",
+		"locale": "en-GB",
+		"intent": {
+			"name": "OrderIntent",
+			"confirmationStatus": "NONE",
+			"slots": {
+				"drink": {
+					"name": "drink",
+					"value": "milky coffee",
+					"resolutions": {
+						"resolutionsPerAuthority": [
+							{
+								"authority": "amzn1.er-authority.echo-sdk.dynamic",
+								"status": {
+									"code": "ER_SUCCESS_NO_MATCH"
+								}
+							},
+							{
+								"authority": "amzn1.er-authority.echo-sdk.amzn1.ask.skill.myappid.DrinkType",
+								"status": {
+									"code": "ER_SUCCESS_MATCH"
+								},
+								"values": [
+									{
+										"value": {
+											"name": "Latte",
+											"id": "Latte"
+										}
+									}
+								]
+							}
+						]
+					}
+				},
+				"size": {
+					"name": "size",
+					"value": "grande"
+				},
+				"milk": {
+					"name": "milk"
+				}
+			}
+		}
+	}
+}"#
+    }
+}
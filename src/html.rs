@@ -0,0 +1,163 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value;
+use super::apl::{Transformer, TransformerType};
+use std::collections::HashMap;
+
+/// Alexa.Presentation.HTML.Start directive corresponding to the [Alexa Web API for Games spec](https://developer.amazon.com/docs/alexa-web-api-for-games/alexa-presentation-html-interface.html#start),
+/// loading the web app on the device
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Start {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    transformers: Vec<Transformer>,
+    request: StartRequest,
+    configuration: Configuration,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StartRequest {
+    uri: String,
+    method: String,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Configuration {
+    #[serde(rename = "timeoutInSeconds")]
+    timeout_in_seconds: u32,
+}
+
+impl Start {
+    /// Constructs the directive loading the web app at `uri`, closed after
+    /// `timeout_in_seconds` without user interaction
+    pub fn new(uri: &str, timeout_in_seconds: u32) -> Start {
+        Start {
+            data: None,
+            transformers: Vec::new(),
+            request: StartRequest {
+                uri: String::from(uri),
+                method: String::from("GET"),
+                headers: HashMap::new(),
+            },
+            configuration: Configuration { timeout_in_seconds },
+        }
+    }
+
+    /// sets the startup data available to the web app, any JSON value
+    pub fn data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// adds a header sent when loading the web app, e.g. an authorization
+    /// token
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.request
+            .headers
+            .insert(String::from(name), String::from(value));
+        self
+    }
+
+    /// adds a transformer applied to the data
+    pub fn transformer(
+        mut self,
+        input_path: &str,
+        output_name: &str,
+        transformer: TransformerType,
+    ) -> Self {
+        self.transformers
+            .push(Transformer::new(input_path, output_name, transformer));
+        self
+    }
+}
+
+/// Alexa.Presentation.HTML.HandleMessage directive corresponding to the [Alexa Web API for Games spec](https://developer.amazon.com/docs/alexa-web-api-for-games/alexa-presentation-html-interface.html#handle),
+/// sending a message to the running web app
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct HandleMessage {
+    message: Value,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    transformers: Vec<Transformer>,
+}
+
+impl HandleMessage {
+    /// Constructs the directive sending the given JSON message
+    pub fn new(message: Value) -> HandleMessage {
+        HandleMessage {
+            message,
+            transformers: Vec::new(),
+        }
+    }
+
+    /// adds a transformer, e.g. turning a text property of the message into
+    /// speech the web app can play
+    pub fn transformer(
+        mut self,
+        input_path: &str,
+        output_name: &str,
+        transformer: TransformerType,
+    ) -> Self {
+        self.transformers
+            .push(Transformer::new(input_path, output_name, transformer));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start() {
+        let d = Start::new("https://example.com/game/index.html", 300)
+            .header("Authorization", "Bearer abc")
+            .data(serde_json::json!({
+                "level": {"id": 3, "tiles": [[0, 1], [1, 0]]},
+                "players": ["Ann", "Bob"]
+            }));
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["request"]["uri"], "https://example.com/game/index.html");
+        assert_eq!(v["request"]["method"], "GET");
+        assert_eq!(v["request"]["headers"]["Authorization"], "Bearer abc");
+        assert_eq!(v["configuration"]["timeoutInSeconds"], 300);
+        assert_eq!(v["data"]["level"]["tiles"][0][1], 1);
+        assert_eq!(v["data"]["players"][1], "Bob");
+        assert!(v.get("transformers").is_none());
+
+        let v = serde_json::to_value(Start::new("https://example.com", 60)).unwrap();
+        assert!(v.get("data").is_none());
+        assert!(v["request"].get("headers").is_none());
+    }
+
+    #[test]
+    fn test_handle_message() {
+        let d = HandleMessage::new(serde_json::json!({
+            "event": "score",
+            "players": [{"name": "Ann", "score": 3}],
+            "prompt": "<speak>Ann leads</speak>"
+        }))
+        .transformer("prompt", "promptSpeech", TransformerType::SsmlToSpeech);
+        let v = serde_json::to_value(&d).unwrap();
+        assert_eq!(v["message"]["players"][0]["score"], 3);
+        assert_eq!(v["transformers"][0]["inputPath"], "prompt");
+        assert_eq!(v["transformers"][0]["outputName"], "promptSpeech");
+        assert_eq!(v["transformers"][0]["transformer"], "ssmlToSpeech");
+
+        let v = serde_json::to_value(HandleMessage::new(Value::from("ping"))).unwrap();
+        assert_eq!(v["message"], "ping");
+        assert!(v.get("transformers").is_none());
+    }
+}
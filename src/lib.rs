@@ -7,7 +7,7 @@
 //! 
 //! Simplest possible Alexa "Hello, World" skill:
 //!
-//! ```rust
+//! ```rust,ignore
 //! extern crate lambda_runtime as lambda;
 //! extern crate alexa_sdk;
 //!
@@ -28,7 +28,7 @@
 //!
 //! A more complete skill, handling multiple locales and a slot:
 //!
-//! ```rust
+//! ```rust,ignore
 //! extern crate lambda_runtime as lambda;
 //! extern crate alexa_sdk;
 //!
@@ -46,7 +46,7 @@
 //!         Locale::AustralianEnglish => Response::simple("hello", "G'day mate"),
 //!         Locale::German => Response::simple("hello", "Hallo Welt"),
 //!         Locale::Japanese => Response::simple("hello", "こんにちは世界"),
-//!         _ => if let Some(ref s) = req.slot_value("name") {
+//!         _ => if let Some(s) = req.slot_value("name") {
 //!             Response::simple("hello", (String::from("hello ") + s).as_str())
 //!         } else {
 //!             Response::simple("hello", "hello world")
@@ -74,8 +74,30 @@
 //! }
 //! ```
 
+pub mod apl;
+pub mod builder;
+pub mod can_fulfill;
+pub mod dialog;
+pub mod display;
+#[macro_use]
+pub mod entities;
+pub mod error;
+pub mod gadgets;
+pub mod handler;
+pub mod html;
+pub mod presentation;
+pub mod proactive;
+pub mod purchasing;
 pub mod request;
+#[macro_use]
 pub mod response;
+pub mod slot_types;
+pub mod smarthome;
+pub mod speech_markdown;
+pub mod ssml;
+pub mod timers;
+pub mod video;
 
+pub use self::error::Error;
 pub use self::request::{Request};
 pub use self::response::{Response};
\ No newline at end of file
@@ -0,0 +1,288 @@
+use super::request::{Request, Viewport};
+use std::fmt;
+
+/// Supported interface name of the Alexa Web API for Games
+pub const HTML_INTERFACE: &str = "Alexa.Presentation.HTML";
+/// Supported interface name of the Alexa Presentation Language
+pub const APL_INTERFACE: &str = "Alexa.Presentation.APL";
+
+/// Presentation path a skill should take for the requesting device
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Presentation {
+    /// the device can run a web app (Alexa Web API for Games)
+    Web,
+    /// the device has a screen supporting APL
+    Apl,
+    /// voice only
+    Voice,
+}
+
+impl Presentation {
+    /// picks the richest presentation supported by the requesting device
+    pub fn best_for(req: &Request) -> Presentation {
+        if req.supports_html() {
+            Presentation::Web
+        } else if req.supports_apl() && req.viewport().is_some() {
+            Presentation::Apl
+        } else {
+            Presentation::Voice
+        }
+    }
+
+    /// returns true if the presentation has a screen
+    pub fn is_visual(&self) -> bool {
+        *self != Presentation::Voice
+    }
+}
+
+/// Standard APL viewport profiles, see the
+/// [viewport profiles reference](https://developer.amazon.com/docs/alexa-presentation-language/apl-alexa-viewport-profiles-package.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewportProfile {
+    HubRoundSmall,
+    HubLandscapeSmall,
+    HubLandscapeMedium,
+    HubLandscapeLarge,
+    MobileLandscapeSmall,
+    MobilePortraitSmall,
+    MobileLandscapeMedium,
+    MobilePortraitMedium,
+    MobileLandscapeLarge,
+    MobilePortraitLarge,
+    TvLandscapeMedium,
+    TvLandscapeXLarge,
+    TvPortraitMedium,
+    Unknown,
+}
+
+impl fmt::Display for ViewportProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            ViewportProfile::HubRoundSmall => "HUB_ROUND_SMALL",
+            ViewportProfile::HubLandscapeSmall => "HUB_LANDSCAPE_SMALL",
+            ViewportProfile::HubLandscapeMedium => "HUB_LANDSCAPE_MEDIUM",
+            ViewportProfile::HubLandscapeLarge => "HUB_LANDSCAPE_LARGE",
+            ViewportProfile::MobileLandscapeSmall => "MOBILE_LANDSCAPE_SMALL",
+            ViewportProfile::MobilePortraitSmall => "MOBILE_PORTRAIT_SMALL",
+            ViewportProfile::MobileLandscapeMedium => "MOBILE_LANDSCAPE_MEDIUM",
+            ViewportProfile::MobilePortraitMedium => "MOBILE_PORTRAIT_MEDIUM",
+            ViewportProfile::MobileLandscapeLarge => "MOBILE_LANDSCAPE_LARGE",
+            ViewportProfile::MobilePortraitLarge => "MOBILE_PORTRAIT_LARGE",
+            ViewportProfile::TvLandscapeMedium => "TV_LANDSCAPE_MEDIUM",
+            ViewportProfile::TvLandscapeXLarge => "TV_LANDSCAPE_XLARGE",
+            ViewportProfile::TvPortraitMedium => "TV_PORTRAIT_MEDIUM",
+            ViewportProfile::Unknown => "UNKNOWN_VIEWPORT_PROFILE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum Size {
+    XSmall,
+    Small,
+    Medium,
+    Large,
+    XLarge,
+}
+
+impl From<u32> for Size {
+    fn from(pixels: u32) -> Size {
+        match pixels {
+            0..=599 => Size::XSmall,
+            600..=959 => Size::Small,
+            960..=1279 => Size::Medium,
+            1280..=1919 => Size::Large,
+            _ => Size::XLarge,
+        }
+    }
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum Density {
+    XLow,
+    Low,
+    Medium,
+    High,
+    XHigh,
+    XXHigh,
+}
+
+impl From<u32> for Density {
+    fn from(dpi: u32) -> Density {
+        match dpi {
+            0..=120 => Density::XLow,
+            121..=160 => Density::Low,
+            161..=240 => Density::Medium,
+            241..=320 => Density::High,
+            321..=480 => Density::XHigh,
+            _ => Density::XXHigh,
+        }
+    }
+}
+
+impl ViewportProfile {
+    /// classifies a viewport by shape, orientation, size and pixel density
+    pub fn of(viewport: &Viewport) -> ViewportProfile {
+        let (width, height, dpi) = match (
+            viewport.current_pixel_width.or(viewport.pixel_width),
+            viewport.current_pixel_height.or(viewport.pixel_height),
+            viewport.dpi,
+        ) {
+            (Some(w), Some(h), Some(d)) => (w, h, d),
+            _ => return ViewportProfile::Unknown,
+        };
+        let round = viewport.shape.as_deref() == Some("ROUND");
+        let landscape = width > height;
+        let portrait = width < height;
+        let (w, h, d) = (Size::from(width), Size::from(height), Density::from(dpi));
+
+        if round {
+            if w == Size::XSmall
+                && h == Size::XSmall
+                && d == Density::Low
+                && !landscape
+                && !portrait
+            {
+                return ViewportProfile::HubRoundSmall;
+            }
+            return ViewportProfile::Unknown;
+        }
+        if landscape && d == Density::Low {
+            if w <= Size::Medium && h <= Size::XSmall {
+                return ViewportProfile::HubLandscapeSmall;
+            }
+            if w <= Size::Medium && h <= Size::Small {
+                return ViewportProfile::HubLandscapeMedium;
+            }
+            if w >= Size::Large && h >= Size::Small {
+                return ViewportProfile::HubLandscapeLarge;
+            }
+        }
+        if d == Density::Medium {
+            if landscape && w >= Size::Medium && h >= Size::Small {
+                return ViewportProfile::MobileLandscapeLarge;
+            }
+            if portrait && w >= Size::Small && h >= Size::Medium {
+                return ViewportProfile::MobilePortraitLarge;
+            }
+            if landscape && w >= Size::Small && h >= Size::XSmall {
+                return ViewportProfile::MobileLandscapeMedium;
+            }
+            if portrait && w >= Size::XSmall && h >= Size::Small {
+                return ViewportProfile::MobilePortraitMedium;
+            }
+        }
+        if d == Density::Low {
+            if landscape {
+                return ViewportProfile::MobileLandscapeSmall;
+            }
+            if portrait {
+                return ViewportProfile::MobilePortraitSmall;
+            }
+        }
+        if d >= Density::High {
+            if landscape && w >= Size::XLarge && h >= Size::Medium {
+                return ViewportProfile::TvLandscapeXLarge;
+            }
+            if portrait && w == Size::XSmall && h == Size::XLarge {
+                return ViewportProfile::TvPortraitMedium;
+            }
+            if landscape && w == Size::Medium && h == Size::Small {
+                return ViewportProfile::TvLandscapeMedium;
+            }
+        }
+        ViewportProfile::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(interfaces: &str, viewport: bool) -> Request {
+        let mut v = serde_json::json!({
+            "version": "1.0",
+            "context": {
+                "System": {
+                    "device": {
+                        "deviceId": "amzn1.ask.device.1",
+                        "supportedInterfaces": serde_json::from_str::<serde_json::Value>(interfaces).unwrap()
+                    }
+                }
+            },
+            "request": {
+                "type": "LaunchRequest",
+                "requestId": "amzn1.echo-api.request.1",
+                "timestamp": "2019-03-01T10:00:00Z",
+                "locale": "en-US"
+            }
+        });
+        if viewport {
+            v["context"]["Viewport"] = serde_json::json!({"pixelWidth": 1024});
+        }
+        serde_json::from_value(v).unwrap()
+    }
+
+    #[test]
+    fn test_best_for() {
+        let web = req(
+            r#"{"Alexa.Presentation.HTML": {}, "Alexa.Presentation.APL": {}}"#,
+            true,
+        );
+        assert_eq!(Presentation::best_for(&web), Presentation::Web);
+        let apl = req(r#"{"Alexa.Presentation.APL": {}}"#, true);
+        assert_eq!(Presentation::best_for(&apl), Presentation::Apl);
+        let voice = req(r#"{"AudioPlayer": {}}"#, false);
+        assert_eq!(Presentation::best_for(&voice), Presentation::Voice);
+        assert!(!Presentation::best_for(&voice).is_visual());
+    }
+
+    fn viewport(shape: &str, width: u32, height: u32, dpi: u32) -> Viewport {
+        serde_json::from_value(serde_json::json!({
+            "shape": shape,
+            "pixelWidth": width,
+            "pixelHeight": height,
+            "dpi": dpi,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_viewport_profiles() {
+        let cases = [
+            (
+                viewport("ROUND", 480, 480, 160),
+                ViewportProfile::HubRoundSmall,
+            ),
+            (
+                viewport("RECTANGLE", 960, 480, 160),
+                ViewportProfile::HubLandscapeSmall,
+            ),
+            (
+                viewport("RECTANGLE", 1024, 600, 160),
+                ViewportProfile::HubLandscapeMedium,
+            ),
+            (
+                viewport("RECTANGLE", 1280, 800, 160),
+                ViewportProfile::HubLandscapeLarge,
+            ),
+            (
+                viewport("RECTANGLE", 1920, 1080, 320),
+                ViewportProfile::TvLandscapeXLarge,
+            ),
+            (
+                viewport("RECTANGLE", 1024, 600, 240),
+                ViewportProfile::MobileLandscapeLarge,
+            ),
+            (viewport("ROUND", 1024, 600, 160), ViewportProfile::Unknown),
+        ];
+        for (v, expected) in cases.iter() {
+            assert_eq!(ViewportProfile::of(v), *expected);
+        }
+        assert_eq!(
+            ViewportProfile::HubLandscapeMedium.to_string(),
+            "HUB_LANDSCAPE_MEDIUM"
+        );
+    }
+}
@@ -0,0 +1,724 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Proactive event payload for the [Proactive Events API](https://developer.amazon.com/docs/smapi/proactive-events-api.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ProactiveEvent {
+    timestamp: String,
+    #[serde(rename = "referenceId")]
+    reference_id: String,
+    #[serde(rename = "expiryTime")]
+    expiry_time: String,
+    event: Event,
+    #[serde(rename = "localizedAttributes")]
+    localized_attributes: Vec<HashMap<String, String>>,
+    #[serde(rename = "relevantAudience")]
+    relevant_audience: Audience,
+}
+
+impl ProactiveEvent {
+    /// Constructs a multicast proactive event; timestamps are ISO 8601 and
+    /// `reference_id` must be unique per event instance
+    pub fn new(
+        reference_id: &str,
+        timestamp: &str,
+        expiry_time: &str,
+        event: Event,
+    ) -> ProactiveEvent {
+        ProactiveEvent {
+            timestamp: String::from(timestamp),
+            reference_id: String::from(reference_id),
+            expiry_time: String::from(expiry_time),
+            event,
+            localized_attributes: Vec::new(),
+            relevant_audience: Audience::multicast(),
+        }
+    }
+
+    /// adds the localized attributes for a locale
+    /// the keys are referenced by the event payload, see the `Event`
+    /// constructors for which keys each schema needs
+    pub fn localized(mut self, locale: &str, attributes: &[(&str, &str)]) -> Self {
+        let mut h = HashMap::new();
+        h.insert(String::from("locale"), String::from(locale));
+        for (k, v) in attributes {
+            h.insert(String::from(*k), String::from(*v));
+        }
+        self.localized_attributes.push(h);
+        self
+    }
+
+    /// sends the event to a single user instead of all subscribed users
+    pub fn unicast(mut self, user_id: &str) -> Self {
+        self.relevant_audience = Audience::unicast(user_id);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Audience {
+    #[serde(rename = "type")]
+    audience_type: String,
+    payload: HashMap<String, String>,
+}
+
+impl Audience {
+    fn multicast() -> Audience {
+        Audience {
+            audience_type: String::from("Multicast"),
+            payload: HashMap::new(),
+        }
+    }
+
+    fn unicast(user_id: &str) -> Audience {
+        let mut payload = HashMap::new();
+        payload.insert(String::from("user"), String::from(user_id));
+        Audience {
+            audience_type: String::from("Unicast"),
+            payload,
+        }
+    }
+}
+
+/// A schema-specific event name and payload
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Event {
+    name: String,
+    payload: Value,
+}
+
+fn localized(key: &str) -> String {
+    format!("localizedattribute:{}", key)
+}
+
+impl Event {
+    /// Constructs an AMAZON.WeatherAlert.Activated event
+    /// requires the `source` localized attribute
+    pub fn weather_alert(alert_type: WeatherAlertType) -> Event {
+        Event {
+            name: String::from("AMAZON.WeatherAlert.Activated"),
+            payload: serde_json::json!({
+                "weatherAlert": {
+                    "source": localized("source"),
+                    "alertType": alert_type.to_string(),
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.OrderStatus.Updated event
+    /// requires the `sellerName` localized attribute
+    pub fn order_status(status: OrderStatus, expected_arrival: Option<&str>) -> Event {
+        let mut state = serde_json::json!({ "status": status.to_string() });
+        if let Some(arrival) = expected_arrival {
+            state["deliveryDetails"] = serde_json::json!({ "expectedArrival": arrival });
+        }
+        Event {
+            name: String::from("AMAZON.OrderStatus.Updated"),
+            payload: serde_json::json!({
+                "state": state,
+                "order": {
+                    "seller": { "name": localized("sellerName") }
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.MessageAlert.Activated event
+    pub fn message_alert(
+        creator: &str,
+        count: u32,
+        status: MessageStatus,
+        freshness: MessageFreshness,
+        urgent: bool,
+    ) -> Event {
+        let mut group = serde_json::json!({
+            "creator": { "name": creator },
+            "count": count,
+        });
+        if urgent {
+            group["urgency"] = Value::from("URGENT");
+        }
+        Event {
+            name: String::from("AMAZON.MessageAlert.Activated"),
+            payload: serde_json::json!({
+                "state": {
+                    "status": status.to_string(),
+                    "freshness": freshness.to_string(),
+                },
+                "messageGroup": group,
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.TrashCollectionAlert.Activated event
+    pub fn trash_collection_alert(garbage_types: &[GarbageType], day_of_week: DayOfWeek) -> Event {
+        let types: Vec<String> = garbage_types.iter().map(|g| g.to_string()).collect();
+        Event {
+            name: String::from("AMAZON.TrashCollectionAlert.Activated"),
+            payload: serde_json::json!({
+                "alert": {
+                    "garbageTypes": types,
+                    "collectionDayOfWeek": day_of_week.to_string(),
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.MediaContent.Available event
+    /// requires the `providerName` and `contentName` localized attributes
+    pub fn media_content(
+        start_time: &str,
+        method: MediaMethod,
+        content_type: MediaContentType,
+    ) -> Event {
+        Event {
+            name: String::from("AMAZON.MediaContent.Available"),
+            payload: serde_json::json!({
+                "availability": {
+                    "startTime": start_time,
+                    "provider": { "name": localized("providerName") },
+                    "method": method.to_string(),
+                },
+                "content": {
+                    "name": localized("contentName"),
+                    "contentType": content_type.to_string(),
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.SportsEvent.Updated event for a score change
+    /// requires the `eventLeagueName` localized attribute
+    pub fn sports_event(
+        home_team: (&str, u32),
+        away_team: (&str, u32),
+        scoring_team: &str,
+        score_earned: u32,
+    ) -> Event {
+        Event {
+            name: String::from("AMAZON.SportsEvent.Updated"),
+            payload: serde_json::json!({
+                "update": {
+                    "scoreEarned": score_earned,
+                    "teamName": scoring_team,
+                },
+                "sportsEvent": {
+                    "eventLeague": { "name": localized("eventLeagueName") },
+                    "homeTeamStatistic": {
+                        "team": { "name": home_team.0 },
+                        "score": home_team.1,
+                    },
+                    "awayTeamStatistic": {
+                        "team": { "name": away_team.0 },
+                        "score": away_team.1,
+                    },
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.Occasion.Updated event
+    /// requires the `subject`, `providerName` and `brokerName` localized
+    /// attributes
+    pub fn occasion(
+        status: OccasionStatus,
+        occasion_type: OccasionType,
+        booking_time: &str,
+    ) -> Event {
+        Event {
+            name: String::from("AMAZON.Occasion.Updated"),
+            payload: serde_json::json!({
+                "state": { "confirmationStatus": status.to_string() },
+                "occasion": {
+                    "occasionType": occasion_type.to_string(),
+                    "subject": localized("subject"),
+                    "provider": { "name": localized("providerName") },
+                    "bookingTime": booking_time,
+                    "broker": { "name": localized("brokerName") },
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.GameInvite.Available event
+    /// requires the `gameName` localized attribute
+    pub fn game_invite(
+        inviter: &str,
+        relationship: InviteeRelationship,
+        invite_type: InviteType,
+    ) -> Event {
+        Event {
+            name: String::from("AMAZON.GameInvite.Available"),
+            payload: serde_json::json!({
+                "invite": {
+                    "relationshipToInvitee": relationship.to_string(),
+                    "inviter": { "name": inviter },
+                    "inviteType": invite_type.to_string(),
+                    "game": { "name": localized("gameName") },
+                }
+            }),
+        }
+    }
+
+    /// Constructs an AMAZON.SocialGameInvite.Available event
+    /// requires the `gameName` localized attribute
+    pub fn social_game_invite(
+        inviter: &str,
+        relationship: InviteeRelationship,
+        invite_type: InviteType,
+        offer: GameOffer,
+    ) -> Event {
+        Event {
+            name: String::from("AMAZON.SocialGameInvite.Available"),
+            payload: serde_json::json!({
+                "invite": {
+                    "relationshipToInvitee": relationship.to_string(),
+                    "inviter": { "name": inviter },
+                    "inviteType": invite_type.to_string(),
+                },
+                "game": {
+                    "offer": offer.to_string(),
+                    "name": localized("gameName"),
+                }
+            }),
+        }
+    }
+}
+
+/// Alert types of the WeatherAlert schema
+pub enum WeatherAlertType {
+    Default,
+    Tornado,
+    Hurricane,
+    SnowStorm,
+    ThunderStorm,
+}
+
+impl fmt::Display for WeatherAlertType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            WeatherAlertType::Default => "DEFAULT",
+            WeatherAlertType::Tornado => "TORNADO",
+            WeatherAlertType::Hurricane => "HURRICANE",
+            WeatherAlertType::SnowStorm => "SNOW_STORM",
+            WeatherAlertType::ThunderStorm => "THUNDER_STORM",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Order states of the OrderStatus schema
+pub enum OrderStatus {
+    PreorderReceived,
+    OrderReceived,
+    OrderPreparing,
+    OrderShipped,
+    OrderOutForDelivery,
+    OrderDelivered,
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            OrderStatus::PreorderReceived => "PREORDER_RECEIVED",
+            OrderStatus::OrderReceived => "ORDER_RECEIVED",
+            OrderStatus::OrderPreparing => "ORDER_PREPARING",
+            OrderStatus::OrderShipped => "ORDER_SHIPPED",
+            OrderStatus::OrderOutForDelivery => "ORDER_OUT_FOR_DELIVERY",
+            OrderStatus::OrderDelivered => "ORDER_DELIVERED",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Message states of the MessageAlert schema
+pub enum MessageStatus {
+    Unread,
+    Flagged,
+}
+
+impl fmt::Display for MessageStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            MessageStatus::Unread => "UNREAD",
+            MessageStatus::Flagged => "FLAGGED",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Message freshness of the MessageAlert schema
+pub enum MessageFreshness {
+    New,
+    Overdue,
+}
+
+impl fmt::Display for MessageFreshness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            MessageFreshness::New => "NEW",
+            MessageFreshness::Overdue => "OVERDUE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Garbage types of the TrashCollectionAlert schema
+pub enum GarbageType {
+    Bottles,
+    Bulky,
+    Burnable,
+    Cans,
+    Cardboard,
+    Chemicals,
+    Compostable,
+    Crushable,
+    GardenWaste,
+    Glass,
+    Hazardous,
+    HomeAppliances,
+    KitchenWaste,
+    Landfill,
+    PetBottles,
+    RecyclablePlastics,
+    Recycling,
+    WastePaper,
+}
+
+impl fmt::Display for GarbageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            GarbageType::Bottles => "BOTTLES",
+            GarbageType::Bulky => "BULKY",
+            GarbageType::Burnable => "BURNABLE",
+            GarbageType::Cans => "CANS",
+            GarbageType::Cardboard => "CARDBOARD",
+            GarbageType::Chemicals => "CHEMICALS",
+            GarbageType::Compostable => "COMPOSTABLE",
+            GarbageType::Crushable => "CRUSHABLE",
+            GarbageType::GardenWaste => "GARDEN_WASTE",
+            GarbageType::Glass => "GLASS",
+            GarbageType::Hazardous => "HAZARDOUS",
+            GarbageType::HomeAppliances => "HOME_APPLIANCES",
+            GarbageType::KitchenWaste => "KITCHEN_WASTE",
+            GarbageType::Landfill => "LANDFILL",
+            GarbageType::PetBottles => "PET_BOTTLES",
+            GarbageType::RecyclablePlastics => "RECYCLABLE_PLASTICS",
+            GarbageType::Recycling => "RECYCLING",
+            GarbageType::WastePaper => "WASTE_PAPER",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Collection days of the TrashCollectionAlert schema
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl fmt::Display for DayOfWeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            DayOfWeek::Monday => "MONDAY",
+            DayOfWeek::Tuesday => "TUESDAY",
+            DayOfWeek::Wednesday => "WEDNESDAY",
+            DayOfWeek::Thursday => "THURSDAY",
+            DayOfWeek::Friday => "FRIDAY",
+            DayOfWeek::Saturday => "SATURDAY",
+            DayOfWeek::Sunday => "SUNDAY",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Availability methods of the MediaContent schema
+pub enum MediaMethod {
+    Stream,
+    Air,
+    Release,
+    Premiere,
+    Drop,
+}
+
+impl fmt::Display for MediaMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            MediaMethod::Stream => "STREAM",
+            MediaMethod::Air => "AIR",
+            MediaMethod::Release => "RELEASE",
+            MediaMethod::Premiere => "PREMIERE",
+            MediaMethod::Drop => "DROP",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Content types of the MediaContent schema
+pub enum MediaContentType {
+    Book,
+    Episode,
+    Album,
+    Single,
+    Movie,
+    Game,
+}
+
+impl fmt::Display for MediaContentType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            MediaContentType::Book => "BOOK",
+            MediaContentType::Episode => "EPISODE",
+            MediaContentType::Album => "ALBUM",
+            MediaContentType::Single => "SINGLE",
+            MediaContentType::Movie => "MOVIE",
+            MediaContentType::Game => "GAME",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Confirmation states of the Occasion schema
+pub enum OccasionStatus {
+    Confirmed,
+    Canceled,
+    Rescheduled,
+    Requested,
+    Created,
+    Updated,
+}
+
+impl fmt::Display for OccasionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            OccasionStatus::Confirmed => "CONFIRMED",
+            OccasionStatus::Canceled => "CANCELED",
+            OccasionStatus::Rescheduled => "RESCHEDULED",
+            OccasionStatus::Requested => "REQUESTED",
+            OccasionStatus::Created => "CREATED",
+            OccasionStatus::Updated => "UPDATED",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Occasion types of the Occasion schema
+pub enum OccasionType {
+    ReservationRequest,
+    Reservation,
+    AppointmentRequest,
+    Appointment,
+}
+
+impl fmt::Display for OccasionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            OccasionType::ReservationRequest => "RESERVATION_REQUEST",
+            OccasionType::Reservation => "RESERVATION",
+            OccasionType::AppointmentRequest => "APPOINTMENT_REQUEST",
+            OccasionType::Appointment => "APPOINTMENT",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Relationship of the inviter to the invitee in the game invite schemas
+pub enum InviteeRelationship {
+    Friend,
+    Contact,
+}
+
+impl fmt::Display for InviteeRelationship {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            InviteeRelationship::Friend => "FRIEND",
+            InviteeRelationship::Contact => "CONTACT",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Invite types of the game invite schemas
+pub enum InviteType {
+    Challenge,
+    Invite,
+}
+
+impl fmt::Display for InviteType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            InviteType::Challenge => "CHALLENGE",
+            InviteType::Invite => "INVITE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Game offers of the SocialGameInvite schema
+pub enum GameOffer {
+    Match,
+    Rematch,
+    Game,
+}
+
+impl fmt::Display for GameOffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            GameOffer::Match => "MATCH",
+            GameOffer::Rematch => "REMATCH",
+            GameOffer::Game => "GAME",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_alert() {
+        let ev = ProactiveEvent::new(
+            "ref-1",
+            "2018-06-18T22:10:01.00Z",
+            "2018-06-19T22:10:01.00Z",
+            Event::weather_alert(WeatherAlertType::Tornado),
+        )
+        .localized("en-US", &[("source", "Weather Service")])
+        .localized("de-DE", &[("source", "Wetterdienst")]);
+        let v = serde_json::to_value(&ev).unwrap();
+        assert_eq!(v["referenceId"], "ref-1");
+        assert_eq!(v["event"]["name"], "AMAZON.WeatherAlert.Activated");
+        assert_eq!(
+            v["event"]["payload"]["weatherAlert"]["source"],
+            "localizedattribute:source"
+        );
+        assert_eq!(
+            v["event"]["payload"]["weatherAlert"]["alertType"],
+            "TORNADO"
+        );
+        assert_eq!(v["localizedAttributes"][1]["locale"], "de-DE");
+        assert_eq!(v["localizedAttributes"][1]["source"], "Wetterdienst");
+        assert_eq!(v["relevantAudience"]["type"], "Multicast");
+    }
+
+    #[test]
+    fn test_order_status_unicast() {
+        let ev = ProactiveEvent::new(
+            "ref-2",
+            "2018-06-18T22:10:01.00Z",
+            "2018-06-19T22:10:01.00Z",
+            Event::order_status(OrderStatus::OrderShipped, Some("2018-12-14T23:32:00.463Z")),
+        )
+        .unicast("amzn1.ask.account.theuserid");
+        let v = serde_json::to_value(&ev).unwrap();
+        let p = &v["event"]["payload"];
+        assert_eq!(p["state"]["status"], "ORDER_SHIPPED");
+        assert_eq!(
+            p["state"]["deliveryDetails"]["expectedArrival"],
+            "2018-12-14T23:32:00.463Z"
+        );
+        assert_eq!(
+            p["order"]["seller"]["name"],
+            "localizedattribute:sellerName"
+        );
+        assert_eq!(v["relevantAudience"]["type"], "Unicast");
+        assert_eq!(
+            v["relevantAudience"]["payload"]["user"],
+            "amzn1.ask.account.theuserid"
+        );
+    }
+
+    #[test]
+    fn test_message_alert() {
+        let e = Event::message_alert(
+            "Andy",
+            5,
+            MessageStatus::Unread,
+            MessageFreshness::New,
+            false,
+        );
+        assert_eq!(e.payload["messageGroup"]["count"], 5);
+        assert_eq!(e.payload["state"]["freshness"], "NEW");
+        assert!(e.payload["messageGroup"].get("urgency").is_none());
+    }
+
+    #[test]
+    fn test_trash_collection_alert() {
+        let e = Event::trash_collection_alert(
+            &[GarbageType::Recycling, GarbageType::GardenWaste],
+            DayOfWeek::Tuesday,
+        );
+        assert_eq!(e.payload["alert"]["garbageTypes"][1], "GARDEN_WASTE");
+        assert_eq!(e.payload["alert"]["collectionDayOfWeek"], "TUESDAY");
+    }
+
+    #[test]
+    fn test_sports_event() {
+        let e = Event::sports_event(("Real Madrid", 1), ("FC Barcelona", 0), "Real Madrid", 1);
+        assert_eq!(e.name, "AMAZON.SportsEvent.Updated");
+        assert_eq!(e.payload["update"]["teamName"], "Real Madrid");
+        assert_eq!(
+            e.payload["sportsEvent"]["eventLeague"]["name"],
+            "localizedattribute:eventLeagueName"
+        );
+        assert_eq!(
+            e.payload["sportsEvent"]["awayTeamStatistic"]["team"]["name"],
+            "FC Barcelona"
+        );
+        assert_eq!(e.payload["sportsEvent"]["homeTeamStatistic"]["score"], 1);
+    }
+
+    #[test]
+    fn test_occasion() {
+        let e = Event::occasion(
+            OccasionStatus::Confirmed,
+            OccasionType::Appointment,
+            "2018-11-20T19:16:31Z",
+        );
+        assert_eq!(e.name, "AMAZON.Occasion.Updated");
+        assert_eq!(e.payload["state"]["confirmationStatus"], "CONFIRMED");
+        assert_eq!(e.payload["occasion"]["occasionType"], "APPOINTMENT");
+        assert_eq!(
+            e.payload["occasion"]["broker"]["name"],
+            "localizedattribute:brokerName"
+        );
+    }
+
+    #[test]
+    fn test_game_invites() {
+        let e = Event::game_invite("Max", InviteeRelationship::Friend, InviteType::Challenge);
+        assert_eq!(e.name, "AMAZON.GameInvite.Available");
+        assert_eq!(e.payload["invite"]["inviteType"], "CHALLENGE");
+        assert_eq!(
+            e.payload["invite"]["game"]["name"],
+            "localizedattribute:gameName"
+        );
+
+        let e = Event::social_game_invite(
+            "Max",
+            InviteeRelationship::Contact,
+            InviteType::Invite,
+            GameOffer::Rematch,
+        );
+        assert_eq!(e.name, "AMAZON.SocialGameInvite.Available");
+        assert_eq!(e.payload["invite"]["relationshipToInvitee"], "CONTACT");
+        assert_eq!(e.payload["game"]["offer"], "REMATCH");
+        assert!(e.payload["invite"].get("game").is_none());
+    }
+}
@@ -0,0 +1,112 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Connections.SendRequest directive starting an [in-skill purchasing](https://developer.amazon.com/docs/in-skill-purchase/add-isps-to-a-skill.html)
+/// flow; the result arrives as a Connections.Response request carrying `token`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SendRequest {
+    name: String,
+    payload: PurchasePayload,
+    token: String,
+}
+
+/// Purchase flow launched by a `SendRequest`
+pub enum PurchaseAction {
+    Buy,
+    Upsell,
+    Cancel,
+}
+
+impl fmt::Display for PurchaseAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            PurchaseAction::Buy => "Buy",
+            PurchaseAction::Upsell => "Upsell",
+            PurchaseAction::Cancel => "Cancel",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchasePayload {
+    #[serde(rename = "InSkillProduct")]
+    in_skill_product: InSkillProduct,
+    #[serde(rename = "upsellMessage")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upsell_message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InSkillProduct {
+    #[serde(rename = "productId")]
+    product_id: String,
+}
+
+impl SendRequest {
+    /// Constructs a request offering the product for purchase
+    pub fn buy(product_id: &str, token: &str) -> SendRequest {
+        SendRequest::new(PurchaseAction::Buy, product_id, None, token)
+    }
+
+    /// Constructs a request suggesting the product with the given message
+    /// while the user does something else
+    pub fn upsell(product_id: &str, message: &str, token: &str) -> SendRequest {
+        SendRequest::new(PurchaseAction::Upsell, product_id, Some(message), token)
+    }
+
+    /// Constructs a request cancelling (or refunding) the product
+    pub fn cancel(product_id: &str, token: &str) -> SendRequest {
+        SendRequest::new(PurchaseAction::Cancel, product_id, None, token)
+    }
+
+    fn new(
+        action: PurchaseAction,
+        product_id: &str,
+        upsell_message: Option<&str>,
+        token: &str,
+    ) -> SendRequest {
+        SendRequest {
+            name: action.to_string(),
+            payload: PurchasePayload {
+                in_skill_product: InSkillProduct {
+                    product_id: String::from(product_id),
+                },
+                upsell_message: upsell_message.map(String::from),
+            },
+            token: String::from(token),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purchase_requests() {
+        let v = serde_json::to_value(SendRequest::buy("amzn1.adg.product.1", "buy-1")).unwrap();
+        assert_eq!(v["name"], "Buy");
+        assert_eq!(
+            v["payload"]["InSkillProduct"]["productId"],
+            "amzn1.adg.product.1"
+        );
+        assert!(v["payload"].get("upsellMessage").is_none());
+        assert_eq!(v["token"], "buy-1");
+
+        let d = SendRequest::upsell("amzn1.adg.product.1", "Want more levels?", "upsell-1");
+        let v = serde_json::to_value(d).unwrap();
+        assert_eq!(v["name"], "Upsell");
+        assert_eq!(v["payload"]["upsellMessage"], "Want more levels?");
+
+        let v = serde_json::to_value(SendRequest::cancel("amzn1.adg.product.1", "c-1")).unwrap();
+        assert_eq!(v["name"], "Cancel");
+    }
+}
@@ -2,173 +2,1105 @@ extern crate serde;
 extern crate serde_derive;
 extern crate serde_json;
 
+use self::serde::de::DeserializeOwned;
 use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value as JsonValue;
+use super::error::Error;
+use super::presentation::{ViewportProfile, APL_INTERFACE, HTML_INTERFACE};
+use super::slot_types::{self, SlotDate, SlotDuration, SlotError, SlotTime};
 use std::collections::HashMap;
 use std::convert::From;
+use std::fmt;
+use std::io::Read;
+use std::str::FromStr;
+use std::time::Duration;
 
 /// Request struct corresponding to the [Alexa spec](https://developer.amazon.com/docs/custom-skills/request-and-response-json-reference.html#request-body-parameters)
+///
+/// Fields not modeled by the request structs are kept in their `extra` maps,
+/// so a parsed request re-serializes to the same JSON (e.g. when proxying)
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Request {
     pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub session: Option<Session>,
     #[serde(rename = "request")]
     pub body: ReqBody,
     pub context: Context,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Session {
     pub new: bool,
     #[serde(rename = "sessionId")]
     pub session_id: String,
-    pub attributes: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<HashMap<String, JsonValue>>,
     pub application: Application,
     pub user: User,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Application {
     #[serde(rename = "applicationId")]
     pub application_id: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct User {
     #[serde(rename = "userId")]
     pub user_id: String,
     #[serde(rename = "accessToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub access_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
+/// Permissions the user granted to the skill
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Permissions {
+    #[serde(rename = "consentToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent_token: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Device {
     #[serde(rename = "deviceId")]
     pub device_id: String,
+    #[serde(rename = "persistentEndpointId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_endpoint_id: Option<String>,
+    #[serde(rename = "supportedInterfaces")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_interfaces: Option<HashMap<String, JsonValue>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ReqBody {
     #[serde(rename = "type")]
     pub reqtype: String,
     #[serde(rename = "requestId")]
     pub request_id: String,
     pub timestamp: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub locale: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub intent: Option<Intent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
     #[serde(rename = "dialogState")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dialog_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<Task>,
+    #[serde(rename = "eventCreationTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_creation_time: Option<String>,
+    #[serde(rename = "eventPublishingTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_publishing_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<JsonValue>,
+    #[serde(rename = "originatingRequestId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub originating_request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RequestError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cause: Option<Cause>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Vec<JsonValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<UserEventSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<HashMap<String, JsonValue>>,
+    #[serde(rename = "correlationToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_token: Option<String>,
+    #[serde(rename = "listId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_id: Option<String>,
+    #[serde(rename = "startIndex")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    #[serde(rename = "pageToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<AplError>>,
+    #[serde(rename = "expirationPayload")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_payload: Option<JsonValue>,
+    #[serde(rename = "apiRequest")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_request: Option<ApiRequest>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Error reported by a System.ExceptionEncountered, SessionEndedRequest or
+/// AudioPlayer.PlaybackFailed request
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RequestError {
+    #[serde(rename = "type")]
+    pub error_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Request whose response caused a System.ExceptionEncountered
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Cause {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Component that sent an Alexa.Presentation.APL.UserEvent request
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UserEventSource {
+    #[serde(rename = "type")]
+    pub source_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<JsonValue>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Error reported by an Alexa.Presentation.APL.RuntimeError request
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AplError {
+    #[serde(rename = "type")]
+    pub error_type: String,
+    pub reason: String,
+    #[serde(rename = "listId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Task passed on a LaunchRequest started from a quick link or skill connection
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Task {
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<JsonValue>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl Task {
+    /// parses the task input into a typed parameter struct, if present and valid
+    pub fn params<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.input.clone()?).ok()
+    }
+}
+
+/// Body of an AlexaHouseholdListEvent request
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ListEvent {
+    #[serde(rename = "listId")]
+    pub list_id: String,
+    #[serde(rename = "listItemIds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_item_ids: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
+/// API invocation of an Alexa Conversations Dialog.API.Invoked request
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApiRequest {
+    pub name: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, JsonValue>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub slots: HashMap<String, JsonValue>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl ApiRequest {
+    /// parses the named argument into a typed value, if present and valid
+    pub fn argument<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        serde_json::from_value(self.arguments.get(name)?.clone()).ok()
+    }
+}
+
+/// Body of a reminder lifecycle event request
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReminderEvent {
+    #[serde(rename = "alertToken")]
+    pub alert_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Event reported by a GameEngine input handler
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InputHandlerEvent {
+    pub name: String,
+    #[serde(rename = "inputEvents")]
+    pub input_events: Vec<InputEvent>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InputEvent {
+    #[serde(rename = "gadgetId")]
+    pub gadget_id: String,
+    pub timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+    pub action: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Event sent by a gadget through a custom interface
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CustomEvent {
+    pub header: CustomEventHeader,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<CustomEventEndpoint>,
+    #[serde(default)]
+    pub payload: JsonValue,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CustomEventHeader {
+    pub namespace: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CustomEventEndpoint {
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Context {
     #[serde(rename = "System")]
     pub system: System,
     #[serde(rename = "AudioPlayer")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub audio_player: Option<AudioPlayer>,
+    #[serde(rename = "Viewport")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<Viewport>,
+    #[serde(rename = "Viewports")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewports: Option<Vec<ViewportEntry>>,
+    #[serde(rename = "Automotive")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automotive: Option<Automotive>,
+    #[serde(rename = "Extensions")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Extensions>,
+    #[serde(rename = "Alexa.Presentation.APL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apl: Option<AplVisualContext>,
+    #[serde(rename = "Experimentation")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimentation: Option<Experimentation>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// State of the vehicle, present when the request comes from Alexa Auto
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Automotive {
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// What an APL document currently displays, see the
+/// [visual context reference](https://developer.amazon.com/docs/alexa-presentation-language/apl-visual-context.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AplVisualContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(rename = "componentsVisibleOnScreen")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components_visible_on_screen: Option<Vec<VisibleComponent>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl AplVisualContext {
+    /// all visible components, parents before their children
+    pub fn components(&self) -> Vec<&VisibleComponent> {
+        let mut all = Vec::new();
+        if let Some(ref c) = self.components_visible_on_screen {
+            for component in c {
+                component.collect(&mut all);
+            }
+        }
+        all
+    }
+
+    /// the visible component tagged with the given ordinal, e.g. 3 for
+    /// "select the third one"
+    pub fn component_by_ordinal(&self, ordinal: u64) -> Option<&VisibleComponent> {
+        self.components()
+            .into_iter()
+            .find(|c| c.ordinal() == Some(ordinal))
+    }
+}
+
+/// Component of an APL document visible on screen
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VisibleComponent {
+    pub uid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<HashMap<String, JsonValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Vec<JsonValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<VisibleComponent>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl VisibleComponent {
+    /// the position of the component in a list, from its `ordinal` tag
+    pub fn ordinal(&self) -> Option<u64> {
+        self.tags.as_ref()?.get("ordinal")?.as_u64()
+    }
+
+    fn collect<'a>(&'a self, all: &mut Vec<&'a VisibleComponent>) {
+        all.push(self);
+        if let Some(ref children) = self.children {
+            for child in children {
+                child.collect(all);
+            }
+        }
+    }
+}
+
+/// APL extensions available on the device, keyed by extension URI
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Extensions {
+    #[serde(default)]
+    pub available: HashMap<String, JsonValue>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Skill experiments the request takes part in, see
+/// [A/B testing](https://developer.amazon.com/docs/custom-skills/ab-testing.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Experimentation {
+    #[serde(rename = "activeExperiments")]
+    #[serde(default)]
+    pub active_experiments: Vec<ActiveExperiment>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// An experiment and the treatment assigned to the user, e.g. "C" for the
+/// control group or "T1"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ActiveExperiment {
+    pub id: String,
+    #[serde(rename = "treatmentId")]
+    pub treatment_id: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Screen of the device, corresponding to the [Viewport spec](https://developer.amazon.com/docs/alexa-presentation-language/apl-viewport-property.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Viewport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+    #[serde(rename = "pixelWidth")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_width: Option<u32>,
+    #[serde(rename = "pixelHeight")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_height: Option<u32>,
+    #[serde(rename = "currentPixelWidth")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_pixel_width: Option<u32>,
+    #[serde(rename = "currentPixelHeight")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_pixel_height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dpi: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub touch: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyboard: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct System {
+    #[serde(rename = "apiEndpoint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_endpoint: Option<String>,
     #[serde(rename = "apiAccessToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub api_access_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub device: Option<Device>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub application: Option<Application>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub person: Option<Person>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<Unit>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Entry of the `context.Viewports` array: an APL screen (type "APL") or an
+/// APLT character display (type "APLT")
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ViewportEntry {
+    #[serde(rename = "type")]
+    pub viewport_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dpi: Option<u32>,
+    #[serde(rename = "presentationType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_type: Option<String>,
+    #[serde(rename = "canRotate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_rotate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration: Option<ViewportConfiguration>,
+    #[serde(rename = "supportedProfiles")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_profiles: Option<Vec<String>>,
+    #[serde(rename = "lineLength")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_length: Option<u32>,
+    #[serde(rename = "lineCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl ViewportEntry {
+    pub fn is_apl(&self) -> bool {
+        self.viewport_type == "APL"
+    }
+
+    pub fn is_aplt(&self) -> bool {
+        self.viewport_type == "APLT"
+    }
+
+    /// current size in pixels of an APL viewport, if reported
+    pub fn pixel_size(&self) -> Option<(u32, u32)> {
+        let size = self
+            .configuration
+            .as_ref()?
+            .current
+            .as_ref()?
+            .size
+            .as_ref()?;
+        Some((size.pixel_width?, size.pixel_height?))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ViewportConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<ViewportCurrent>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ViewportCurrent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ViewportSize>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ViewportSize {
+    #[serde(rename = "type")]
+    pub size_type: String,
+    #[serde(rename = "pixelWidth")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_width: Option<u32>,
+    #[serde(rename = "pixelHeight")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_height: Option<u32>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Speaker recognized by their voice profile
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Person {
+    #[serde(rename = "personId")]
+    pub person_id: String,
+    #[serde(rename = "accessToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Organizational unit (e.g. a hotel room) of an Alexa Smart Properties device
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Unit {
+    #[serde(rename = "unitId")]
+    pub unit_id: String,
+    #[serde(rename = "persistentUnitId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_unit_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AudioPlayer {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
     #[serde(rename = "offsetInMilliseconds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub offset_in_milliseconds: Option<u64>,
     #[serde(rename = "playerActivity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub player_activity: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl AudioPlayer {
+    /// token of the current (or last played) stream
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// playback position in the current stream, 0 if not reported
+    pub fn offset_in_milliseconds(&self) -> u64 {
+        self.offset_in_milliseconds.unwrap_or(0)
+    }
+
+    pub fn player_activity(&self) -> PlayerActivity {
+        match &self.player_activity {
+            Some(a) => PlayerActivity::from(a.as_str()),
+            None => PlayerActivity::Idle,
+        }
+    }
+}
+
+/// State of the AudioPlayer
+#[derive(Debug, PartialEq)]
+pub enum PlayerActivity {
+    Idle,
+    Paused,
+    Playing,
+    BufferUnderrun,
+    Finished,
+    Stopped,
+    Other(String),
+}
+
+impl<'a> From<&'a str> for PlayerActivity {
+    fn from(s: &'a str) -> PlayerActivity {
+        match s {
+            "IDLE" => PlayerActivity::Idle,
+            "PAUSED" => PlayerActivity::Paused,
+            "PLAYING" => PlayerActivity::Playing,
+            "BUFFER_UNDERRUN" => PlayerActivity::BufferUnderrun,
+            "FINISHED" => PlayerActivity::Finished,
+            "STOPPED" => PlayerActivity::Stopped,
+            _ => PlayerActivity::Other(s.to_string()),
+        }
+    }
+}
+
+/// State of a multi-turn dialog delegated to Alexa
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogState {
+    Started,
+    InProgress,
+    Completed,
+    Other(String),
+}
+
+impl<'a> From<&'a str> for DialogState {
+    fn from(s: &'a str) -> DialogState {
+        match s {
+            "STARTED" => DialogState::Started,
+            "IN_PROGRESS" => DialogState::InProgress,
+            "COMPLETED" => DialogState::Completed,
+            _ => DialogState::Other(s.to_string()),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Intent {
     pub name: String,
     #[serde(rename = "confirmationStatus")]
-    pub confirmation_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<ConfirmationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub slots: Option<HashMap<String, Slot>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+/// Whether the user confirmed an intent or slot value
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ConfirmationStatus {
+    #[default]
+    #[serde(rename = "NONE")]
+    None,
+    #[serde(rename = "CONFIRMED")]
+    Confirmed,
+    #[serde(rename = "DENIED")]
+    Denied,
 }
 
 impl Intent {
+    /// the confirmation status of the intent, `None` if not given
+    pub fn confirmation_status(&self) -> ConfirmationStatus {
+        self.confirmation_status.unwrap_or_default()
+    }
+
     fn get_slot(&self, name: &str) -> Option<&Slot> {
         self.slots.as_ref()?.get(name)
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Slot {
     pub name: String,
-    pub value: String,
+    /// missing when the slot has not been filled yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
     #[serde(rename = "confirmationStatus")]
-    pub confirmation_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<ConfirmationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolutions: Option<Resolution>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl Slot {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// the spoken value, if the slot has been filled
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref().filter(|v| !v.is_empty())
+    }
+
+    /// the confirmation status of the slot value, `None` if not given
+    pub fn confirmation_status(&self) -> ConfirmationStatus {
+        self.confirmation_status.unwrap_or_default()
+    }
+
+    /// parses the value of an AMAZON.DATE slot
+    pub fn as_date(&self) -> Result<SlotDate, SlotError> {
+        slot_types::filled(self.value())?.parse()
+    }
+
+    /// parses the value of an AMAZON.TIME slot
+    pub fn as_time(&self) -> Result<SlotTime, SlotError> {
+        slot_types::filled(self.value())?.parse()
+    }
+
+    /// parses the value of an AMAZON.NUMBER or AMAZON.Ordinal slot
+    pub fn as_i64(&self) -> Result<i64, SlotError> {
+        let v = slot_types::filled(self.value())?;
+        v.parse().map_err(|_| SlotError::Invalid(String::from(v)))
+    }
+
+    /// parses the value of a numeric slot as a float
+    pub fn as_f64(&self) -> Result<f64, SlotError> {
+        let v = slot_types::filled(self.value())?;
+        v.parse().map_err(|_| SlotError::Invalid(String::from(v)))
+    }
+
+    /// parses the value of an AMAZON.DURATION slot, keeping year and month
+    /// components apart
+    pub fn as_calendar_duration(&self) -> Result<SlotDuration, SlotError> {
+        slot_types::filled(self.value())?.parse()
+    }
+
+    /// parses the value of an AMAZON.DURATION slot into a `Duration`; fails
+    /// for durations with year or month components
+    pub fn as_duration(&self) -> Result<Duration, SlotError> {
+        self.as_calendar_duration()?
+            .to_std()
+            .ok_or_else(|| SlotError::Invalid(self.value.clone().unwrap_or_default()))
+    }
+
+    pub fn resolutions(&self) -> Option<&Resolution> {
+        self.resolutions.as_ref()
+    }
+
+    /// first value successfully matched by entity resolution, across all
+    /// authorities
+    fn resolved(&self) -> Option<&Value> {
+        self.matched_values().next()
+    }
+
+    /// entity resolution results of all authorities
+    pub fn resolutions_per_authority(&self) -> impl Iterator<Item = &ResolutionsPerAuthority> {
+        self.resolutions
+            .as_ref()
+            .into_iter()
+            .flat_map(|r| r.resolutions_per_authority.iter())
+    }
+
+    /// values matched by entity resolution, across all authorities
+    pub fn matched_values(&self) -> impl Iterator<Item = &Value> {
+        self.matched_values_where(|_| true)
+    }
+
+    /// values matched against the slot type defined in the interaction model
+    pub fn static_values(&self) -> impl Iterator<Item = &Value> {
+        self.matched_values_where(|r| !r.is_dynamic())
+    }
+
+    /// values matched against dynamic entities
+    pub fn dynamic_values(&self) -> impl Iterator<Item = &Value> {
+        self.matched_values_where(ResolutionsPerAuthority::is_dynamic)
+    }
+
+    fn matched_values_where<F>(&self, filter: F) -> impl Iterator<Item = &Value>
+    where
+        F: Fn(&ResolutionsPerAuthority) -> bool,
+    {
+        self.resolutions_per_authority()
+            .filter(move |r| r.status.code() == StatusCode::SuccessMatch && filter(r))
+            .flat_map(|r| r.values.iter().map(|v| &v.value))
+    }
+
+    /// ID of the entity the slot value resolved to, if any
+    pub fn resolved_id(&self) -> Option<&str> {
+        self.resolved().map(|v| v.id.as_str())
+    }
+
+    /// canonical name of the entity the slot value resolved to, if any
+    pub fn resolved_name(&self) -> Option<&str> {
+        self.resolved().map(|v| v.name.as_str())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Resolution {
     #[serde(rename = "resolutionsPerAuthority")]
     pub resolutions_per_authority: Vec<ResolutionsPerAuthority>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResolutionsPerAuthority {
     pub authority: String,
     pub status: Status,
+    /// left out by Alexa when the status is ER_SUCCESS_NO_MATCH
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub values: Vec<ValueWrapper>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl ResolutionsPerAuthority {
+    /// returns true for the authority of dynamic entities, false for slot
+    /// types of the interaction model
+    pub fn is_dynamic(&self) -> bool {
+        self.authority
+            .starts_with("amzn1.er-authority.echo-sdk.dynamic")
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Status {
     pub code: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+impl Status {
+    pub fn code(&self) -> StatusCode {
+        StatusCode::from(self.code.as_str())
+    }
+}
+
+/// Outcome of entity resolution for one authority
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatusCode {
+    SuccessMatch,
+    SuccessNoMatch,
+    ErrorTimeout,
+    ErrorException,
+    Other(String),
+}
+
+impl<'a> From<&'a str> for StatusCode {
+    fn from(s: &'a str) -> StatusCode {
+        match s {
+            "ER_SUCCESS_MATCH" => StatusCode::SuccessMatch,
+            "ER_SUCCESS_NO_MATCH" => StatusCode::SuccessNoMatch,
+            "ER_ERROR_TIMEOUT" => StatusCode::ErrorTimeout,
+            "ER_ERROR_EXCEPTION" => StatusCode::ErrorException,
+            _ => StatusCode::Other(s.to_string()),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ValueWrapper {
     pub value: Value,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Value {
     pub name: String,
     pub id: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, JsonValue>,
 }
 
 /// Enumeration of Alexa request types
 /// Not comprehensive, ones not defined are put into the Other `String` value
 #[derive(Debug, PartialEq)]
-pub enum ReqType {
+#[non_exhaustive]
+pub enum RequestType {
     LaunchRequest,
     IntentRequest,
     SessionEndedRequest,
     CanFulfillIntentRequest,
+    ListItemsCreated,
+    ListItemsUpdated,
+    ListItemsDeleted,
+    ListCreated,
+    ListUpdated,
+    ListDeleted,
+    InputHandlerEvent,
+    PlaybackStarted,
+    PlaybackFinished,
+    PlaybackStopped,
+    PlaybackNearlyFinished,
+    PlaybackFailed,
+    NextCommandIssued,
+    PauseCommandIssued,
+    PlayCommandIssued,
+    PreviousCommandIssued,
+    SkillEnabled,
+    SkillDisabled,
+    SkillAccountLinked,
+    SkillPermissionAccepted,
+    SkillPermissionChanged,
+    ExceptionEncountered,
+    ElementSelected,
+    UserEvent,
+    LoadIndexListData,
+    LoadTokenListData,
+    RuntimeError,
+    EventsReceived,
+    Expired,
+    ReminderStarted,
+    ReminderCreated,
+    ReminderUpdated,
+    ReminderDeleted,
+    ReminderStatusChanged,
+    ApiInvoked,
     Other(String),
 }
 
-impl<'a> From<&'a str> for ReqType {
-    fn from(s: &'a str) -> ReqType {
+/// Former name of `RequestType`
+pub type ReqType = RequestType;
+
+impl<'a> From<&'a str> for RequestType {
+    fn from(s: &'a str) -> RequestType {
         match s {
-            "LaunchRequest" => ReqType::LaunchRequest,
-            "IntentRequest" => ReqType::IntentRequest,
-            "SessionEndedRequest" => ReqType::SessionEndedRequest,
-            "CanFulfillIntentRequest" => ReqType::CanFulfillIntentRequest,
-            _ => ReqType::Other(s.to_string()),
+            "LaunchRequest" => RequestType::LaunchRequest,
+            "IntentRequest" => RequestType::IntentRequest,
+            "SessionEndedRequest" => RequestType::SessionEndedRequest,
+            "CanFulfillIntentRequest" => RequestType::CanFulfillIntentRequest,
+            "AlexaHouseholdListEvent.ItemsCreated" => RequestType::ListItemsCreated,
+            "AlexaHouseholdListEvent.ItemsUpdated" => RequestType::ListItemsUpdated,
+            "AlexaHouseholdListEvent.ItemsDeleted" => RequestType::ListItemsDeleted,
+            "AlexaHouseholdListEvent.ListCreated" => RequestType::ListCreated,
+            "AlexaHouseholdListEvent.ListUpdated" => RequestType::ListUpdated,
+            "AlexaHouseholdListEvent.ListDeleted" => RequestType::ListDeleted,
+            "GameEngine.InputHandlerEvent" => RequestType::InputHandlerEvent,
+            "AudioPlayer.PlaybackStarted" => RequestType::PlaybackStarted,
+            "AudioPlayer.PlaybackFinished" => RequestType::PlaybackFinished,
+            "AudioPlayer.PlaybackStopped" => RequestType::PlaybackStopped,
+            "AudioPlayer.PlaybackNearlyFinished" => RequestType::PlaybackNearlyFinished,
+            "AudioPlayer.PlaybackFailed" => RequestType::PlaybackFailed,
+            "PlaybackController.NextCommandIssued" => RequestType::NextCommandIssued,
+            "PlaybackController.PauseCommandIssued" => RequestType::PauseCommandIssued,
+            "PlaybackController.PlayCommandIssued" => RequestType::PlayCommandIssued,
+            "PlaybackController.PreviousCommandIssued" => RequestType::PreviousCommandIssued,
+            "AlexaSkillEvent.SkillEnabled" => RequestType::SkillEnabled,
+            "AlexaSkillEvent.SkillDisabled" => RequestType::SkillDisabled,
+            "AlexaSkillEvent.SkillAccountLinked" => RequestType::SkillAccountLinked,
+            "AlexaSkillEvent.SkillPermissionAccepted" => RequestType::SkillPermissionAccepted,
+            "AlexaSkillEvent.SkillPermissionChanged" => RequestType::SkillPermissionChanged,
+            "System.ExceptionEncountered" => RequestType::ExceptionEncountered,
+            "Display.ElementSelected" => RequestType::ElementSelected,
+            "Alexa.Presentation.APL.UserEvent" => RequestType::UserEvent,
+            "Alexa.Presentation.APL.LoadIndexListData" => RequestType::LoadIndexListData,
+            "Alexa.Presentation.APL.LoadTokenListData" => RequestType::LoadTokenListData,
+            "Alexa.Presentation.APL.RuntimeError" => RequestType::RuntimeError,
+            "CustomInterfaceController.EventsReceived" => RequestType::EventsReceived,
+            "CustomInterfaceController.Expired" => RequestType::Expired,
+            "Dialog.API.Invoked" => RequestType::ApiInvoked,
+            "AlexaReminderEvent.ReminderStarted" | "Reminders.ReminderStarted" => {
+                RequestType::ReminderStarted
+            }
+            "AlexaReminderEvent.ReminderCreated" | "Reminders.ReminderCreated" => {
+                RequestType::ReminderCreated
+            }
+            "AlexaReminderEvent.ReminderUpdated" | "Reminders.ReminderUpdated" => {
+                RequestType::ReminderUpdated
+            }
+            "AlexaReminderEvent.ReminderDeleted" | "Reminders.ReminderDeleted" => {
+                RequestType::ReminderDeleted
+            }
+            "AlexaReminderEvent.ReminderStatusChanged" | "Reminders.ReminderStatusChanged" => {
+                RequestType::ReminderStatusChanged
+            }
+            _ => RequestType::Other(s.to_string()),
         }
     }
 }
 
-impl From<String> for ReqType {
-    fn from(s: String) -> ReqType {
-        ReqType::from(s.as_str())
+impl From<String> for RequestType {
+    fn from(s: String) -> RequestType {
+        RequestType::from(s.as_str())
     }
 }
 
 /// Enumeration of Alexa intent types
 /// Custom intents will be User enum values discrimiated by the `String` value
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum IntentType {
     None,
     Help,
@@ -176,14 +1108,23 @@ pub enum IntentType {
     Fallback,
     LoopOff,
     LoopOn,
+    More,
     NavigateHome,
+    NavigateSettings,
     Next,
     No,
+    PageDown,
+    PageUp,
     Pause,
     Previous,
     Repeat,
     Resume,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+    ScrollUp,
     Select,
+    SendToPhone,
     ShuffleOn,
     ShuffleOff,
     StartOver,
@@ -192,8 +1133,42 @@ pub enum IntentType {
     User(String),
 }
 
+/// Regions hosting the Alexa APIs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Region {
+    NorthAmerica,
+    Europe,
+    FarEast,
+}
+
+impl Region {
+    /// the region serving the given API endpoint, e.g.
+    /// "https://api.eu.amazonalexa.com"
+    pub fn from_endpoint(endpoint: &str) -> Option<Region> {
+        let host = endpoint
+            .trim_start_matches("https://")
+            .trim_end_matches('/');
+        match host {
+            "api.amazonalexa.com" => Some(Region::NorthAmerica),
+            "api.eu.amazonalexa.com" => Some(Region::Europe),
+            "api.fe.amazonalexa.com" => Some(Region::FarEast),
+            _ => None,
+        }
+    }
+
+    /// the base URL of the Alexa APIs in the region
+    pub fn base_url(&self) -> &'static str {
+        match *self {
+            Region::NorthAmerica => "https://api.amazonalexa.com",
+            Region::Europe => "https://api.eu.amazonalexa.com",
+            Region::FarEast => "https://api.fe.amazonalexa.com",
+        }
+    }
+}
+
 /// Alexa standard locales
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Locale {
     Italian,
     German,
@@ -210,38 +1185,89 @@ pub enum Locale {
     French,
     CanadianFrench,
     BrazilianPortuguese,
+    Arabic,
+    Dutch,
     Unknown,
 }
 
 impl Locale {
     /// returns true for all English speaking locals
     pub fn is_english(&self) -> bool {
+        matches!(
+            *self,
+            Locale::AmericanEnglish
+                | Locale::AustralianEnglish
+                | Locale::CanadianEnglish
+                | Locale::BritishEnglish
+                | Locale::IndianEnglish
+        )
+    }
+    pub fn is_french(&self) -> bool {
+        matches!(*self, Locale::French | Locale::CanadianFrench)
+    }
+    pub fn is_spanish(&self) -> bool {
+        matches!(
+            *self,
+            Locale::Spanish | Locale::AmericanSpanish | Locale::MexicanSpanish
+        )
+    }
+    pub fn is_portuguese(&self) -> bool {
+        *self == Locale::BrazilianPortuguese
+    }
+    pub fn is_arabic(&self) -> bool {
+        *self == Locale::Arabic
+    }
+    pub fn is_dutch(&self) -> bool {
+        *self == Locale::Dutch
+    }
+
+    /// the locale as sent by Alexa, e.g. "en-US"
+    pub fn as_str(&self) -> &'static str {
         match *self {
-            Locale::AmericanEnglish => true,
-            Locale::AustralianEnglish => true,
-            Locale::CanadianEnglish => true,
-            Locale::BritishEnglish => true,
-            Locale::IndianEnglish => true,
-            _ => false,
+            Locale::Italian => "it-IT",
+            Locale::German => "de-DE",
+            Locale::AustralianEnglish => "en-AU",
+            Locale::CanadianEnglish => "en-CA",
+            Locale::BritishEnglish => "en-GB",
+            Locale::IndianEnglish => "en-IN",
+            Locale::AmericanEnglish => "en-US",
+            Locale::Japanese => "ja-JP",
+            Locale::Spanish => "es-ES",
+            Locale::MexicanSpanish => "es-MX",
+            Locale::AmericanSpanish => "es-US",
+            Locale::Hindi => "hi-IN",
+            Locale::French => "fr-FR",
+            Locale::CanadianFrench => "fr-CA",
+            Locale::BrazilianPortuguese => "pt-BR",
+            Locale::Arabic => "ar-SA",
+            Locale::Dutch => "nl-NL",
+            Locale::Unknown => "unknown",
         }
     }
-    pub fn is_french(&self) -> bool {
+
+    /// the language part of the locale, e.g. "en" for en-US
+    pub fn language(&self) -> Option<&'static str> {
         match *self {
-            Locale::French => true,
-            Locale::CanadianFrench => true,
-            _ => false,
+            Locale::Unknown => None,
+            _ => self.as_str().split('-').next(),
         }
     }
-    pub fn is_spanish(&self) -> bool {
+
+    /// the region part of the locale, e.g. "US" for en-US
+    pub fn region(&self) -> Option<&'static str> {
         match *self {
-            Locale::Spanish => true,
-            Locale::AmericanSpanish => true,
-            Locale::MexicanSpanish => true,
-            _ => false,
+            Locale::Unknown => None,
+            _ => self.as_str().split('-').nth(1),
         }
     }
 }
 
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 impl<'a> From<&'a str> for Locale {
     fn from(s: &'a str) -> Locale {
         match s {
@@ -253,13 +1279,15 @@ impl<'a> From<&'a str> for Locale {
             "en-IN" => Locale::IndianEnglish,
             "en-US" => Locale::AmericanEnglish,
             "ja-JP" => Locale::Japanese,
-            "hi-HI" => Locale::Hindi,
+            "hi-IN" => Locale::Hindi,
             "es-ES" => Locale::Spanish,
             "es-MX" => Locale::MexicanSpanish,
             "es-US" => Locale::AmericanSpanish,
             "fr-FR" => Locale::French,
             "fr-CA" => Locale::CanadianFrench,
             "pt-BR" => Locale::BrazilianPortuguese,
+            "ar-SA" => Locale::Arabic,
+            "nl-NL" => Locale::Dutch,
             _ => Locale::Unknown,
         }
     }
@@ -271,19 +1299,66 @@ impl From<String> for Locale {
     }
 }
 
+impl FromStr for Request {
+    type Err = Error;
+
+    /// Parses a request from its JSON
+    fn from_str(s: &str) -> Result<Request, Error> {
+        serde_json::from_str(s).map_err(Error::Request)
+    }
+}
+
 impl Request {
+    /// Parses a request from JSON bytes
+    pub fn from_slice(v: &[u8]) -> Result<Request, Error> {
+        serde_json::from_slice(v).map_err(Error::Request)
+    }
+
+    /// Parses a request from a reader of JSON, e.g. stdin or an HTTP body
+    pub fn from_reader<R: Read>(r: R) -> Result<Request, Error> {
+        serde_json::from_reader(r).map_err(Error::Request)
+    }
+
     /// Extracts the request type from the request
-    pub fn reqtype(&self) -> ReqType {
-        ReqType::from(&*self.body.reqtype)
+    pub fn reqtype(&self) -> RequestType {
+        self.request_type()
     }
 
-    /// Extracts the locale from the request
-    pub fn locale(&self) -> Locale {
-        Locale::from(&*self.body.locale)
+    /// Extracts the request type from the request
+    pub fn request_type(&self) -> RequestType {
+        RequestType::from(&*self.body.reqtype)
     }
 
-    /// Extracts the intent from the request
-    pub fn intent(&self) -> IntentType {
+    /// retrieves the unique ID of the request
+    pub fn request_id(&self) -> &str {
+        &self.body.request_id
+    }
+
+    /// retrieves the ISO 8601 timestamp the request was sent at
+    pub fn timestamp(&self) -> &str {
+        &self.body.timestamp
+    }
+
+    /// parses the request timestamp, e.g. to check it is recent
+    #[cfg(feature = "chrono")]
+    pub fn timestamp_parsed(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::parse_from_rfc3339(&self.body.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&chrono::Utc))
+    }
+
+    /// Extracts the locale from the request
+    pub fn locale(&self) -> Locale {
+        Locale::from(&*self.body.locale)
+    }
+
+    /// retrieves the state of the dialog, if the skill has a dialog model
+    pub fn dialog_state(&self) -> Option<DialogState> {
+        self.body.dialog_state.as_deref().map(DialogState::from)
+    }
+
+    /// Extracts the intent from the request
+    pub fn intent(&self) -> IntentType {
         if let Some(ref i) = self.body.intent {
             match i.name.as_str() {
                 "AMAZON.HelpIntent" => IntentType::Help,
@@ -291,14 +1366,23 @@ impl Request {
                 "AMAZON.FallbackIntent" => IntentType::Fallback,
                 "AMAZON.LoopOffIntent" => IntentType::LoopOff,
                 "AMAZON.LoopOnIntent" => IntentType::LoopOn,
+                "AMAZON.MoreIntent" => IntentType::More,
                 "AMAZON.NavigateHomeIntent" => IntentType::NavigateHome,
+                "AMAZON.NavigateSettingsIntent" => IntentType::NavigateSettings,
                 "AMAZON.NextIntent" => IntentType::Next,
                 "AMAZON.NoIntent" => IntentType::No,
+                "AMAZON.PageDownIntent" => IntentType::PageDown,
+                "AMAZON.PageUpIntent" => IntentType::PageUp,
                 "AMAZON.PauseIntent" => IntentType::Pause,
                 "AMAZON.PreviousIntent" => IntentType::Previous,
                 "AMAZON.RepeatIntent" => IntentType::Repeat,
                 "AMAZON.ResumeIntent" => IntentType::Resume,
+                "AMAZON.ScrollDownIntent" => IntentType::ScrollDown,
+                "AMAZON.ScrollLeftIntent" => IntentType::ScrollLeft,
+                "AMAZON.ScrollRightIntent" => IntentType::ScrollRight,
+                "AMAZON.ScrollUpIntent" => IntentType::ScrollUp,
                 "AMAZON.SelectIntent" => IntentType::Select,
+                "AMAZON.SendToPhoneIntent" => IntentType::SendToPhone,
                 "AMAZON.ShuffleOffIntent" => IntentType::ShuffleOff,
                 "AMAZON.ShuffleOnIntent" => IntentType::ShuffleOn,
                 "AMAZON.StartOverIntent" => IntentType::StartOver,
@@ -311,26 +1395,432 @@ impl Request {
         }
     }
 
-    /// retrieves the string value of named slot from the request, if it exists
-    pub fn slot_value(&self, slot: &str) -> Option<String> {
-        Some(
-            self.body
-                .intent
-                .as_ref()?
-                .get_slot(slot)
-                .as_ref()?
-                .value
-                .clone(),
-        )
+    /// retrieves the named slot from the intent, if it exists
+    pub fn slot(&self, name: &str) -> Option<&Slot> {
+        self.body.intent.as_ref()?.get_slot(name)
+    }
+
+    /// retrieves the string value of named slot from the request, if it
+    /// exists and has been filled
+    pub fn slot_value(&self, slot: &str) -> Option<&str> {
+        self.slot(slot)?.value()
+    }
+
+    /// iterates over all slots of the intent, filled or not
+    pub fn slots(&self) -> impl Iterator<Item = &Slot> {
+        self.body
+            .intent
+            .as_ref()
+            .and_then(|i| i.slots.as_ref())
+            .into_iter()
+            .flat_map(|s| s.values())
     }
 
     /// retrieves the attribute value with the given key, if it exists
-    pub fn attribute_value(&self, key: &str) -> Option<&String> {
-        self.session.as_ref()?.attributes.as_ref()?.get(key)
+    pub fn attribute_value(&self, key: &str) -> Option<&JsonValue> {
+        self.attributes()?.get(key)
+    }
+
+    /// retrieves the attribute value with the given key as a string slice,
+    /// if it exists and is a string
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attribute_value(key)?.as_str()
+    }
+
+    /// deserializes the attribute with the given key, e.g. one set with
+    /// `Response::set_attr`; `None` if missing or of another type
+    pub fn attr<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        serde_json::from_value(self.attribute_value(key)?.clone()).ok()
+    }
+
+    /// retrieves all session attributes set on the previous turn, if any
+    pub fn attributes(&self) -> Option<&HashMap<String, JsonValue>> {
+        self.session.as_ref()?.attributes.as_ref()
+    }
+
+    /// retrieves the ID of the user, from the session or, for requests sent
+    /// outside of a session, from the context
+    pub fn user_id(&self) -> Option<&str> {
+        Some(&self.user()?.user_id)
+    }
+
+    /// retrieves the user, from the session or, for requests sent outside
+    /// of a session (e.g. AudioPlayer or skill events), from the context
+    pub fn user(&self) -> Option<&User> {
+        match &self.session {
+            Some(s) => Some(&s.user),
+            None => self.context.system.user.as_ref(),
+        }
+    }
+
+    /// retrieves the token for permissions the user granted (deprecated by
+    /// Amazon in favor of the API access token, but still sent)
+    pub fn consent_token(&self) -> Option<&str> {
+        self.user()?.permissions.as_ref()?.consent_token.as_deref()
+    }
+
+    /// retrieves the ID of the speaker, if Alexa recognized their voice
+    pub fn person_id(&self) -> Option<&str> {
+        Some(&self.context.system.person.as_ref()?.person_id)
+    }
+
+    /// retrieves the OAuth access token of an account-linked user, if any
+    pub fn access_token(&self) -> Option<&str> {
+        self.user()?.access_token.as_deref()
+    }
+
+    /// retrieves the intent Alexa asks the skill whether it can fulfill, if
+    /// this is a CanFulfillIntentRequest
+    pub fn can_fulfill_intent(&self) -> Option<&Intent> {
+        if self.request_type() != RequestType::CanFulfillIntentRequest {
+            return None;
+        }
+        self.body.intent.as_ref()
+    }
+
+    /// retrieves the token of the request, e.g. the token of the element
+    /// selected on a Display.ElementSelected request
+    pub fn token(&self) -> Option<&str> {
+        self.body.token.as_deref()
+    }
+
+    /// retrieves the arguments of the SendEvent command that sent an
+    /// Alexa.Presentation.APL.UserEvent request
+    pub fn arguments(&self) -> &[JsonValue] {
+        self.body.arguments.as_deref().unwrap_or(&[])
+    }
+
+    /// retrieves the component that sent an Alexa.Presentation.APL.UserEvent
+    /// request, if any
+    pub fn user_event_source(&self) -> Option<&UserEventSource> {
+        self.body.source.as_ref()
+    }
+
+    /// retrieves the value of the named component reported with an
+    /// Alexa.Presentation.APL.UserEvent request, e.g. the text of an
+    /// EditText
+    pub fn component_value(&self, id: &str) -> Option<&JsonValue> {
+        self.body.components.as_ref()?.get(id)
+    }
+
+    /// retrieves the token to send back with the list data answering an APL
+    /// LoadIndexListData or LoadTokenListData request
+    pub fn correlation_token(&self) -> Option<&str> {
+        self.body.correlation_token.as_deref()
+    }
+
+    /// retrieves the id of the dynamic list data is requested for
+    pub fn list_id(&self) -> Option<&str> {
+        self.body.list_id.as_deref()
+    }
+
+    /// retrieves the start index and number of the items requested by an
+    /// APL LoadIndexListData request
+    pub fn index_range(&self) -> Option<(i64, u32)> {
+        Some((self.body.start_index?, self.body.count?))
+    }
+
+    /// retrieves the token of the page requested by an APL
+    /// LoadTokenListData request
+    pub fn page_token(&self) -> Option<&str> {
+        self.body.page_token.as_deref()
+    }
+
+    /// retrieves the errors reported by an Alexa.Presentation.APL.RuntimeError
+    /// request
+    pub fn apl_errors(&self) -> &[AplError] {
+        self.body.errors.as_deref().unwrap_or(&[])
+    }
+
+    /// retrieves the error reported by the request, if any
+    pub fn error(&self) -> Option<&RequestError> {
+        self.body.error.as_ref()
+    }
+
+    /// retrieves the ID of the request whose response Alexa rejected, if this
+    /// is a System.ExceptionEncountered request
+    pub fn cause_request_id(&self) -> Option<&str> {
+        Some(&self.body.cause.as_ref()?.request_id)
+    }
+
+    /// retrieves the task the skill was launched with (e.g. from a quick link), if any
+    pub fn task(&self) -> Option<&Task> {
+        self.body.task.as_ref()
+    }
+
+    /// retrieves the list and item IDs of a household list event, if this
+    /// request is one
+    pub fn list_event(&self) -> Option<ListEvent> {
+        if !self.body.reqtype.starts_with("AlexaHouseholdListEvent.") {
+            return None;
+        }
+        serde_json::from_value(self.body.body.clone()?).ok()
+    }
+
+    /// retrieves the API invocation of an Alexa Conversations request, if
+    /// this request is one
+    pub fn api_request(&self) -> Option<&ApiRequest> {
+        self.body.api_request.as_ref()
+    }
+
+    /// retrieves the alert token (and status) of a reminder event, if this
+    /// request is one
+    pub fn reminder_event(&self) -> Option<ReminderEvent> {
+        match self.request_type() {
+            RequestType::ReminderStarted
+            | RequestType::ReminderCreated
+            | RequestType::ReminderUpdated
+            | RequestType::ReminderDeleted
+            | RequestType::ReminderStatusChanged => {}
+            _ => return None,
+        }
+        serde_json::from_value(self.body.body.clone()?).ok()
+    }
+
+    /// retrieves the events of a GameEngine input handler event, if this
+    /// request is one
+    pub fn input_handler_events(&self) -> Option<Vec<InputHandlerEvent>> {
+        if self.request_type() != RequestType::InputHandlerEvent {
+            return None;
+        }
+        serde_json::from_value(self.body.events.clone()?).ok()
+    }
+
+    /// retrieves the gadget events of a CustomInterfaceController.EventsReceived
+    /// request, if this request is one
+    pub fn custom_events(&self) -> Option<Vec<CustomEvent>> {
+        if self.request_type() != RequestType::EventsReceived {
+            return None;
+        }
+        serde_json::from_value(self.body.events.clone()?).ok()
+    }
+
+    /// retrieves the payload of an expired custom interface event handler, if
+    /// one was set when it was started
+    pub fn expiration_payload(&self) -> Option<&JsonValue> {
+        self.body.expiration_payload.as_ref()
+    }
+
+    /// retrieves the ID of the request that started the input handler, if
+    /// this is a GameEngine input handler event
+    pub fn originating_request_id(&self) -> Option<&str> {
+        self.body.originating_request_id.as_deref()
+    }
+
+    /// returns whether the device supports the named interface, e.g.
+    /// "Alexa.Presentation.APL"
+    pub fn supports_interface(&self, name: &str) -> bool {
+        self.context
+            .system
+            .device
+            .as_ref()
+            .and_then(|d| d.supported_interfaces.as_ref())
+            .map(|i| i.contains_key(name))
+            .unwrap_or(false)
+    }
+
+    /// retrieves the ID of the skill the request is for
+    pub fn application_id(&self) -> Option<&str> {
+        match &self.context.system.application {
+            Some(a) => Some(&a.application_id),
+            None => Some(&self.session.as_ref()?.application.application_id),
+        }
+    }
+
+    /// verifies the request is for the given skill: every application ID in
+    /// the session and context must match, and at least one must be present
+    pub fn is_for_application(&self, skill_id: &str) -> bool {
+        let ids = [
+            self.session
+                .as_ref()
+                .map(|s| s.application.application_id.as_str()),
+            self.context
+                .system
+                .application
+                .as_ref()
+                .map(|a| a.application_id.as_str()),
+        ];
+        ids.iter().any(|id| id.is_some()) && ids.iter().flatten().all(|id| *id == skill_id)
+    }
+
+    /// retrieves the base URL of the Alexa APIs for this request's region
+    pub fn api_endpoint(&self) -> Option<&str> {
+        self.context.system.api_endpoint.as_deref()
+    }
+
+    /// retrieves the region of the Alexa APIs for this request, from its
+    /// API endpoint
+    pub fn region(&self) -> Option<Region> {
+        Region::from_endpoint(self.api_endpoint()?)
+    }
+
+    /// retrieves the token authorizing calls to the Alexa APIs on behalf of
+    /// this request
+    pub fn api_access_token(&self) -> Option<&str> {
+        self.context.system.api_access_token.as_deref()
+    }
+
+    /// retrieves the vehicle state, if the request comes from Alexa Auto
+    pub fn automotive(&self) -> Option<&Automotive> {
+        self.context.automotive.as_ref()
+    }
+
+    /// returns true if the request comes from a vehicle, where skills should
+    /// keep answers brief and not rely on the screen
+    pub fn is_automotive(&self) -> bool {
+        self.context.automotive.is_some()
+    }
+
+    /// retrieves the ID of the device the request was sent from, if any
+    pub fn device_id(&self) -> Option<&str> {
+        Some(&self.context.system.device.as_ref()?.device_id)
+    }
+
+    /// returns whether the device has a screen supporting APL
+    pub fn supports_apl(&self) -> bool {
+        self.supports_interface(APL_INTERFACE)
+    }
+
+    /// returns whether the device has a character display supporting APLT
+    pub fn supports_aplt(&self) -> bool {
+        self.supports_interface("Alexa.Presentation.APLT")
+    }
+
+    /// returns whether the device can run web apps (Alexa Web API for Games)
+    pub fn supports_html(&self) -> bool {
+        self.supports_interface(HTML_INTERFACE)
+    }
+
+    /// returns whether the device supports AudioPlayer directives
+    pub fn supports_audio_player(&self) -> bool {
+        self.supports_interface("AudioPlayer")
+    }
+
+    /// returns whether the device supports VideoApp directives
+    pub fn supports_video_app(&self) -> bool {
+        self.supports_interface("VideoApp")
+    }
+
+    /// returns whether the device supports Display templates
+    pub fn supports_display(&self) -> bool {
+        self.supports_interface("Display")
+    }
+
+    /// returns whether the device can share its location
+    pub fn supports_geolocation(&self) -> bool {
+        self.supports_interface("Geolocation")
+    }
+
+    /// retrieves what the APL document on screen displays, if any
+    pub fn apl_context(&self) -> Option<&AplVisualContext> {
+        self.context.apl.as_ref()
+    }
+
+    /// retrieves the token of the APL document on screen, if any
+    pub fn apl_token(&self) -> Option<&str> {
+        self.context.apl.as_ref()?.token.as_deref()
+    }
+
+    /// returns whether the device supports Alexa.DataStore directives,
+    /// e.g. to update widgets
+    pub fn supports_datastore(&self) -> bool {
+        self.supports_interface("Alexa.DataStore")
+    }
+
+    /// returns whether the APL extension with the given URI is available on
+    /// the device, e.g. "alexaext:datastore:10"
+    pub fn supports_extension(&self, uri: &str) -> bool {
+        self.available_extensions().any(|e| e == uri)
+    }
+
+    /// URIs of the APL extensions available on the device
+    pub fn available_extensions(&self) -> impl Iterator<Item = &str> {
+        self.context
+            .extensions
+            .as_ref()
+            .into_iter()
+            .flat_map(|e| e.available.keys().map(|k| k.as_str()))
+    }
+
+    /// the experiments the request takes part in
+    pub fn active_experiments(&self) -> &[ActiveExperiment] {
+        self.context
+            .experimentation
+            .as_ref()
+            .map(|e| e.active_experiments.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// the treatment assigned for the experiment with the given id, if the
+    /// request takes part in it
+    pub fn treatment(&self, experiment_id: &str) -> Option<&str> {
+        self.active_experiments()
+            .iter()
+            .find(|e| e.id == experiment_id)
+            .map(|e| e.treatment_id.as_str())
+    }
+
+    /// retrieves the state of the AudioPlayer, if the device has one
+    pub fn audio_player(&self) -> Option<&AudioPlayer> {
+        self.context.audio_player.as_ref()
+    }
+
+    /// retrieves the screen of the device, if it has one
+    pub fn viewport(&self) -> Option<&Viewport> {
+        self.context.viewport.as_ref()
+    }
+
+    /// retrieves all presentation surfaces reported by the device (APL
+    /// screens and APLT character displays)
+    pub fn viewports(&self) -> &[ViewportEntry] {
+        self.context.viewports.as_deref().unwrap_or(&[])
+    }
+
+    /// classifies the screen of the device into one of the standard APL
+    /// viewport profiles
+    pub fn viewport_profile(&self) -> ViewportProfile {
+        match self.viewport() {
+            Some(v) => ViewportProfile::of(v),
+            None => ViewportProfile::Unknown,
+        }
+    }
+
+    /// retrieves the persistent endpoint ID of an Alexa Smart Properties
+    /// device, which stays the same across re-registrations
+    pub fn persistent_endpoint_id(&self) -> Option<&str> {
+        self.context
+            .system
+            .device
+            .as_ref()?
+            .persistent_endpoint_id
+            .as_deref()
+    }
+
+    /// retrieves the organizational unit of an Alexa Smart Properties device,
+    /// if any
+    pub fn unit(&self) -> Option<&Unit> {
+        self.context.system.unit.as_ref()
+    }
+
+    /// retrieves the ID of the organizational unit, scoped to the skill
+    pub fn unit_id(&self) -> Option<&str> {
+        Some(&self.unit()?.unit_id)
+    }
+
+    /// retrieves the ID of the organizational unit shared across skills, if
+    /// any
+    pub fn persistent_unit_id(&self) -> Option<&str> {
+        self.unit()?.persistent_unit_id.as_deref()
     }
 
     /// returns whether or not this is a new request
     pub fn is_new(&self) -> bool {
+        self.is_new_session()
+    }
+
+    /// returns whether this request starts a new session; false for
+    /// requests sent outside of a session (e.g. list events)
+    pub fn is_new_session(&self) -> bool {
         match &self.session {
             Some(s) => s.new,
             None => false,
@@ -342,12 +1832,108 @@ impl Request {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_str() {
+        let req: Request = default_req().parse().unwrap();
+        assert_eq!(req.locale(), Locale::AmericanEnglish);
+        let req = Request::from_slice(default_req().as_bytes()).unwrap();
+        assert_eq!(req.version, "1.0");
+        let req = Request::from_reader(default_req().as_bytes()).unwrap();
+        assert!(req.session.is_some());
+        match "{\"version\": \"1.0\"}".parse::<Request>() {
+            Err(Error::Request(e)) => assert!(e.is_data()),
+            r => panic!("expected request error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_automotive() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(!req.is_automotive());
+        v["context"]["Automotive"] = serde_json::json!({});
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(req.is_automotive());
+        assert!(req.automotive().unwrap().extra.is_empty());
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+    }
+
+    #[test]
+    fn test_extensions() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(!req.supports_datastore());
+        assert_eq!(req.available_extensions().count(), 0);
+        v["context"]["System"]["device"]["supportedInterfaces"]["Alexa.DataStore"] =
+            serde_json::json!({});
+        v["context"]["Extensions"] = serde_json::json!({
+            "available": {"alexaext:datastore:10": {}}
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(req.supports_datastore());
+        assert!(req.supports_extension("alexaext:datastore:10"));
+        assert!(!req.supports_extension("aplext:backstack:10"));
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+    }
+
+    #[test]
+    fn test_experiments() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(req.active_experiments().is_empty());
+        v["context"]["Experimentation"] = serde_json::json!({
+            "activeExperiments": [
+                {"id": "exp-welcome", "treatmentId": "T1"},
+                {"id": "exp-reprompt", "treatmentId": "C"}
+            ]
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(req.active_experiments().len(), 2);
+        assert_eq!(req.treatment("exp-welcome"), Some("T1"));
+        assert_eq!(req.treatment("exp-reprompt"), Some("C"));
+        assert_eq!(req.treatment("exp-other"), None);
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+    }
+
+    #[test]
+    fn test_apl_visual_context() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert!(req.apl_context().is_none());
+        let req: Request = serde_json::from_str(apl_context_req()).unwrap();
+        assert_eq!(req.apl_token(), Some("planetList"));
+        let apl = req.apl_context().unwrap();
+        assert_eq!(apl.components().len(), 4);
+        let third = apl.component_by_ordinal(3).unwrap();
+        assert_eq!(third.id.as_deref(), Some("saturn"));
+        assert_eq!(third.component_type.as_deref(), Some("text"));
+        assert!(apl.component_by_ordinal(4).is_none());
+    }
+
+    #[test]
+    fn test_unknown_request_type() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        v["request"] = serde_json::json!({
+            "type": "Messaging.MessageReceived",
+            "requestId": "amzn1.echo-api.request.1",
+            "timestamp": "2019-03-01T10:00:00Z",
+            "locale": "en-US",
+            "message": {"text": "hello", "sender": "amzn1.ask.person.1"}
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(
+            req.request_type(),
+            RequestType::Other(String::from("Messaging.MessageReceived"))
+        );
+        assert_eq!(req.body.extra["message"]["text"], "hello");
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+    }
+
     #[test]
     fn test_version() {
         let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
         match p {
             Ok(req) => assert_eq!(req.version, "1.0"),
-            Err(e) => panic!(e.to_string()),
+            Err(e) => panic!("{}", e),
         }
     }
 
@@ -356,7 +1942,7 @@ mod tests {
         let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
         match p {
             Ok(req) => assert_eq!(req.locale(), Locale::AmericanEnglish),
-            Err(e) => panic!(e.to_string()),
+            Err(e) => panic!("{}", e),
         }
     }
 
@@ -365,7 +1951,7 @@ mod tests {
         let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
         match p {
             Ok(req) => assert!(req.locale().is_english()),
-            Err(e) => panic!(e.to_string()),
+            Err(e) => panic!("{}", e),
         }
     }
 
@@ -375,61 +1961,1251 @@ mod tests {
             self::serde_json::from_str(default_spanish_req());
         match p {
             Ok(req) => assert!(req.locale().is_spanish()),
-            Err(e) => panic!(e.to_string()),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_is_french() {
+        let p: Result<Request, serde_json::Error> =
+            self::serde_json::from_str(default_french_req());
+        match p {
+            Ok(req) => assert!(req.locale().is_french()),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_marketplace_locales() {
+        assert_eq!(Locale::from("hi-IN"), Locale::Hindi);
+        assert_eq!(Locale::from("es-MX"), Locale::MexicanSpanish);
+        assert!(Locale::from("ar-SA").is_arabic());
+        assert!(Locale::from("nl-NL").is_dutch());
+        assert!(Locale::from("pt-BR").is_portuguese());
+        assert!(Locale::from("fr-CA").is_french());
+        assert_eq!(Locale::from("xx-XX"), Locale::Unknown);
+    }
+
+    #[test]
+    fn test_locale_strings() {
+        assert_eq!(Locale::AmericanEnglish.to_string(), "en-US");
+        assert_eq!(Locale::CanadianFrench.language(), Some("fr"));
+        assert_eq!(Locale::CanadianFrench.region(), Some("CA"));
+        assert_eq!(Locale::Unknown.language(), None);
+        for l in &["es-US", "hi-IN", "pt-BR", "nl-NL"] {
+            assert_eq!(Locale::from(*l).as_str(), *l);
+        }
+    }
+
+    #[test]
+    fn test_locale_as_key() {
+        let mut greetings = HashMap::new();
+        greetings.insert(Locale::German, "Hallo");
+        greetings.insert(Locale::Dutch, "Hallo");
+        let locale = Locale::from("de-DE");
+        let copy = locale;
+        assert_eq!(greetings.get(&locale), Some(&"Hallo"));
+        assert_eq!(copy, Locale::German);
+        assert!(!greetings.contains_key(&Locale::Japanese));
+    }
+
+    #[test]
+    fn test_intent() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => assert_eq!(req.intent(), IntentType::User(String::from("hello"))),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_builtin_intents() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        for (name, expected) in &[
+            ("AMAZON.ScrollDownIntent", IntentType::ScrollDown),
+            ("AMAZON.PageUpIntent", IntentType::PageUp),
+            ("AMAZON.MoreIntent", IntentType::More),
+            (
+                "AMAZON.NavigateSettingsIntent",
+                IntentType::NavigateSettings,
+            ),
+            ("AMAZON.SendToPhoneIntent", IntentType::SendToPhone),
+        ] {
+            v["request"]["intent"]["name"] = JsonValue::from(*name);
+            let req: Request = serde_json::from_value(v.clone()).unwrap();
+            assert_eq!(req.intent(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_slot() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(req_with_slots());
+        match p {
+            Ok(req) => {
+                assert_eq!(req.slot_value("name"), Some("bob"));
+                assert_eq!(req.slot("name").unwrap().name, "name");
+                assert!(req.slot("missing").is_none());
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_slot_without_value() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        v["request"]["intent"]["slots"]["name"] = serde_json::json!({"name": "name"});
+        let req: Request = serde_json::from_value(v).unwrap();
+        let slot = req.slot("name").unwrap();
+        assert!(slot.value.is_none());
+        assert!(slot.resolutions().is_none());
+        assert_eq!(req.slot_value("name"), None);
+    }
+
+    #[test]
+    fn test_slot_as_date() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("2019-W23");
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(
+            req.slot("name").unwrap().as_date(),
+            Ok(SlotDate::Week {
+                year: 2019,
+                week: 23
+            })
+        );
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("?");
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(
+            req.slot("name").unwrap().as_date(),
+            Err(SlotError::Unrecognized)
+        );
+    }
+
+    #[test]
+    fn test_slot_as_time() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("AF");
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.slot("name").unwrap().as_time(), Ok(SlotTime::Afternoon));
+    }
+
+    #[test]
+    fn test_slot_as_number() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        let slot = |v: &JsonValue| -> Slot {
+            serde_json::from_value(v["request"]["intent"]["slots"]["name"].clone()).unwrap()
+        };
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("-42");
+        assert_eq!(slot(&v).as_i64(), Ok(-42));
+        assert_eq!(slot(&v).as_f64(), Ok(-42.0));
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("2.5");
+        assert_eq!(
+            slot(&v).as_i64(),
+            Err(SlotError::Invalid(String::from("2.5")))
+        );
+        assert_eq!(slot(&v).as_f64(), Ok(2.5));
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("?");
+        assert_eq!(slot(&v).as_i64(), Err(SlotError::Unrecognized));
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::Null;
+        assert_eq!(slot(&v).as_f64(), Err(SlotError::Missing));
+    }
+
+    #[test]
+    fn test_slot_as_duration() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("PT10M");
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(
+            req.slot("name").unwrap().as_duration(),
+            Ok(Duration::from_secs(600))
+        );
+        v["request"]["intent"]["slots"]["name"]["value"] = JsonValue::from("P2M");
+        let req: Request = serde_json::from_value(v).unwrap();
+        let slot = req.slot("name").unwrap();
+        assert!(slot.as_duration().is_err());
+        assert_eq!(slot.as_calendar_duration().unwrap().months, 2);
+    }
+
+    #[test]
+    fn test_confirmation_status() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        v["request"]["intent"]["confirmationStatus"] = JsonValue::from("DENIED");
+        v["request"]["intent"]["slots"]["name"]["confirmationStatus"] =
+            JsonValue::from("CONFIRMED");
+        let req: Request = serde_json::from_value(v).unwrap();
+        let intent = req.body.intent.as_ref().unwrap();
+        assert_eq!(intent.confirmation_status(), ConfirmationStatus::Denied);
+        assert_eq!(
+            req.slot("name").unwrap().confirmation_status(),
+            ConfirmationStatus::Confirmed
+        );
+        assert_eq!(
+            serde_json::to_value(ConfirmationStatus::None).unwrap(),
+            "NONE"
+        );
+    }
+
+    #[test]
+    fn test_resolutions() {
+        let p: Result<Request, serde_json::Error> =
+            self::serde_json::from_str(req_with_resolutions());
+        match p {
+            Ok(req) => {
+                let planet = req.slot("planet").unwrap();
+                assert_eq!(planet.resolved_id(), Some("JUPITER"));
+                assert_eq!(planet.resolved_name(), Some("Jupiter"));
+                let mut names: Vec<&str> = req.slots().map(|s| s.name()).collect();
+                names.sort_unstable();
+                assert_eq!(names, vec!["moon", "planet"]);
+                let moon = req.slot("moon").unwrap();
+                assert_eq!(moon.value(), Some("phobos"));
+                assert_eq!(moon.confirmation_status(), ConfirmationStatus::None);
+                assert!(moon.resolved_id().is_none());
+                assert!(moon.resolved_name().is_none());
+                let authority = moon.resolutions_per_authority().next().unwrap();
+                assert_eq!(authority.status.code(), StatusCode::SuccessNoMatch);
+                assert!(authority.values.is_empty());
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_resolution_authorities() {
+        let mut v: JsonValue = serde_json::from_str(req_with_resolutions()).unwrap();
+        let planet = &mut v["request"]["intent"]["slots"]["planet"];
+        planet["resolutions"]["resolutionsPerAuthority"][0]["status"]["code"] =
+            JsonValue::from("ER_SUCCESS_MATCH");
+        planet["resolutions"]["resolutionsPerAuthority"][0]["values"] =
+            serde_json::json!([{"value": {"name": "Jove", "id": "JOVE"}}]);
+        let req: Request = serde_json::from_value(v).unwrap();
+        let planet = req.slot("planet").unwrap();
+        let authorities: Vec<_> = planet.resolutions_per_authority().collect();
+        assert_eq!(authorities.len(), 2);
+        assert!(authorities[0].is_dynamic());
+        assert_eq!(authorities[1].status.code(), StatusCode::SuccessMatch);
+        let ids = |it: &mut dyn Iterator<Item = &Value>| -> Vec<String> {
+            it.map(|v| v.id.clone()).collect()
+        };
+        assert_eq!(ids(&mut planet.matched_values()), vec!["JOVE", "JUPITER"]);
+        assert_eq!(ids(&mut planet.static_values()), vec!["JUPITER"]);
+        assert_eq!(ids(&mut planet.dynamic_values()), vec!["JOVE"]);
+        assert_eq!(planet.resolved_id(), Some("JOVE"));
+        let moon = req.slot("moon").unwrap();
+        assert_eq!(moon.matched_values().count(), 0);
+        assert_eq!(
+            StatusCode::from("ER_ERROR_TIMEOUT"),
+            StatusCode::ErrorTimeout
+        );
+    }
+
+    #[test]
+    fn test_no_resolutions() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(req_with_slots());
+        match p {
+            Ok(req) => {
+                let name = req.slot("name").unwrap();
+                assert_eq!(name.resolutions_per_authority().count(), 0);
+                assert_eq!(name.matched_values().count(), 0);
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_attribute() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => {
+                assert!(req.session.is_some());
+                assert!(req.session.unwrap().attributes.is_some());
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_attribute_val() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => assert_eq!(
+                req.attribute_value("lastSpeech"),
+                Some(&JsonValue::from(
+                    "Jupiter has the shortest day of all the planets"
+                ))
+            ),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_attributes() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => {
+                assert_eq!(
+                    req.attribute("lastSpeech"),
+                    Some("Jupiter has the shortest day of all the planets")
+                );
+                assert!(req.attribute("missing").is_none());
+                assert_eq!(req.attributes().unwrap().len(), 1);
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_json_attributes() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        v["session"]["attributes"] = serde_json::json!({
+            "count": 3,
+            "game": {"level": 2, "players": ["ann", "bob"]}
+        });
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.attribute_value("count"), Some(&JsonValue::from(3)));
+        assert_eq!(req.attribute_value("game").unwrap()["level"], 2);
+        assert!(req.attribute("count").is_none());
+        assert_eq!(req.attr::<u32>("count"), Some(3));
+        assert_eq!(req.attr::<Vec<String>>("count"), None);
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Game {
+            level: u32,
+            players: Vec<String>,
+        }
+        assert_eq!(
+            req.attr::<Game>("game"),
+            Some(Game {
+                level: 2,
+                players: vec![String::from("ann"), String::from("bob")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_task() {
+        #[derive(Deserialize)]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+        struct SoundParams {
+            sound: String,
+        }
+
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(task_req());
+        match p {
+            Ok(req) => {
+                assert_eq!(req.reqtype(), RequestType::LaunchRequest);
+                let task = req.task().unwrap();
+                assert_eq!(task.name, "AMZN1.ask.skill.myappid.PlaySoundTask");
+                let params: SoundParams = task.params().unwrap();
+                assert_eq!(params.sound, "rain");
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_no_task() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => {
+                assert!(req.task().is_none());
+                assert_eq!(req.slots().count(), 0);
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_list_event() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(list_event_req());
+        match p {
+            Ok(req) => {
+                assert_eq!(req.reqtype(), RequestType::ListItemsCreated);
+                assert!(req.session.is_none());
+                let ev = req.list_event().unwrap();
+                assert_eq!(ev.list_id, "list-id-1");
+                assert_eq!(
+                    ev.list_item_ids,
+                    Some(vec![String::from("item-1"), String::from("item-2")])
+                );
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_list_level_events() {
+        let mut v: JsonValue = serde_json::from_str(list_event_req()).unwrap();
+        v["request"]["body"] = serde_json::json!({"listId": "list-id-2"});
+        for (t, expected) in &[
+            (
+                "AlexaHouseholdListEvent.ListCreated",
+                RequestType::ListCreated,
+            ),
+            (
+                "AlexaHouseholdListEvent.ListUpdated",
+                RequestType::ListUpdated,
+            ),
+            (
+                "AlexaHouseholdListEvent.ListDeleted",
+                RequestType::ListDeleted,
+            ),
+            (
+                "AlexaHouseholdListEvent.ItemsDeleted",
+                RequestType::ListItemsDeleted,
+            ),
+        ] {
+            v["request"]["type"] = JsonValue::from(*t);
+            let req: Request = serde_json::from_value(v.clone()).unwrap();
+            assert_eq!(req.request_type(), *expected);
+            let ev = req.list_event().unwrap();
+            assert_eq!(ev.list_id, "list-id-2");
+            assert!(ev.list_item_ids.is_none());
+        }
+    }
+
+    #[test]
+    fn test_dialog_state() {
+        let mut v: JsonValue = serde_json::from_str(req_with_slots()).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Request>(v.clone())
+                .unwrap()
+                .dialog_state(),
+            None
+        );
+        for (s, expected) in &[
+            ("STARTED", DialogState::Started),
+            ("IN_PROGRESS", DialogState::InProgress),
+            ("COMPLETED", DialogState::Completed),
+            ("PAUSED", DialogState::Other(String::from("PAUSED"))),
+        ] {
+            v["request"]["dialogState"] = JsonValue::from(*s);
+            let req: Request = serde_json::from_value(v.clone()).unwrap();
+            assert_eq!(req.dialog_state().as_ref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_api_invoked() {
+        let req: Request = serde_json::from_str(api_invoked_req()).unwrap();
+        assert_eq!(req.request_type(), RequestType::ApiInvoked);
+        let api = req.api_request().unwrap();
+        assert_eq!(api.name, "GetWeather");
+        assert_eq!(
+            api.argument::<String>("cityName"),
+            Some(String::from("Seattle"))
+        );
+        assert_eq!(api.argument::<u32>("days"), Some(3));
+        assert_eq!(api.argument::<String>("missing"), None);
+        assert_eq!(api.slots["cityName"]["value"], "seattle");
+        assert!(serde_json::from_str::<Request>(default_req())
+            .unwrap()
+            .api_request()
+            .is_none());
+    }
+
+    #[test]
+    fn test_reminder_event() {
+        let mut v: JsonValue = serde_json::from_str(list_event_req()).unwrap();
+        v["request"]["type"] = JsonValue::from("AlexaReminderEvent.ReminderStatusChanged");
+        v["request"]["body"] = serde_json::json!({
+            "alertToken": "amzn1.alexa-reminder.alert.1",
+            "status": "COMPLETED"
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(req.request_type(), RequestType::ReminderStatusChanged);
+        let ev = req.reminder_event().unwrap();
+        assert_eq!(ev.alert_token, "amzn1.alexa-reminder.alert.1");
+        assert_eq!(ev.status.as_deref(), Some("COMPLETED"));
+        assert!(req.list_event().is_none());
+
+        v["request"]["type"] = JsonValue::from("Reminders.ReminderCreated");
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.request_type(), RequestType::ReminderCreated);
+        assert!(req.reminder_event().is_some());
+        assert!(serde_json::from_str::<Request>(list_event_req())
+            .unwrap()
+            .reminder_event()
+            .is_none());
+    }
+
+    #[test]
+    fn test_input_handler_events() {
+        let req: Request = serde_json::from_str(input_handler_req()).unwrap();
+        assert_eq!(req.request_type(), RequestType::InputHandlerEvent);
+        assert_eq!(
+            req.originating_request_id(),
+            Some("amzn1.echo-api.request.1234")
+        );
+        let events = req.input_handler_events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "button_down_event");
+        let input = &events[0].input_events[0];
+        assert_eq!(
+            input.gadget_id,
+            "amzn1.ask.gadget.05RPH7PJG9C61DHI4QR0RLOQOHKGVQ"
+        );
+        assert_eq!(input.action, "down");
+        assert_eq!(input.color.as_deref(), Some("FF0000"));
+        assert!(serde_json::from_str::<Request>(default_req())
+            .unwrap()
+            .input_handler_events()
+            .is_none());
+    }
+
+    #[test]
+    fn test_custom_events() {
+        let req: Request = serde_json::from_str(custom_events_req()).unwrap();
+        assert_eq!(req.request_type(), RequestType::EventsReceived);
+        assert_eq!(req.token(), Some("1234abcd-40bb-11e9-9527-6b98b093d166"));
+        let events = req.custom_events().unwrap();
+        assert_eq!(events[0].header.namespace, "Custom.Robot");
+        assert_eq!(events[0].header.name, "EyeBlink");
+        assert_eq!(
+            events[0].endpoint.as_ref().unwrap().endpoint_id,
+            "amzn1.ask.endpoint.ABCD"
+        );
+        assert_eq!(events[0].payload["ack"], "ok");
+
+        let mut v: JsonValue = serde_json::from_str(custom_events_req()).unwrap();
+        v["request"]["type"] = JsonValue::from("CustomInterfaceController.Expired");
+        v["request"]["expirationPayload"] = serde_json::json!({"gameOver": true});
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.request_type(), RequestType::Expired);
+        assert!(req.custom_events().is_none());
+        assert_eq!(req.expiration_payload().unwrap()["gameOver"], true);
+    }
+
+    #[test]
+    fn test_is_new_session() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert!(req.is_new_session());
+        let req: Request = serde_json::from_str(list_event_req()).unwrap();
+        assert!(!req.is_new_session());
+    }
+
+    #[test]
+    fn test_user_id() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert_eq!(req.user_id(), Some("amzn1.ask.account.theuserid"));
+        let req: Request = serde_json::from_str(list_event_req()).unwrap();
+        assert_eq!(req.user_id(), Some("amzn1.ask.account.theuserid"));
+        assert_eq!(req.consent_token(), Some("consent.token"));
+    }
+
+    #[test]
+    fn test_context_user() {
+        let mut v: JsonValue = serde_json::from_str(list_event_req()).unwrap();
+        v["context"]["System"]["user"]["accessToken"] = JsonValue::from("linked-token");
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert!(req.session.is_none());
+        let user = req.user().unwrap();
+        assert_eq!(user.user_id, "amzn1.ask.account.theuserid");
+        assert_eq!(req.access_token(), Some("linked-token"));
+    }
+
+    #[test]
+    fn test_access_token() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(req.access_token().is_none());
+        v["session"]["user"]["accessToken"] = JsonValue::from("linked-token");
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.access_token(), Some("linked-token"));
+    }
+
+    #[test]
+    fn test_device_id() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert_eq!(req.device_id(), Some("amzn1.ask.device.superfakedevice"));
+        let req: Request = serde_json::from_str(req_with_resolutions()).unwrap();
+        assert!(req.device_id().is_none());
+    }
+
+    #[test]
+    fn test_api_credentials() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert_eq!(req.api_endpoint(), Some("https://api.amazonalexa.com"));
+        assert_eq!(req.api_access_token(), Some("53kr14t.k3y.d4t4-otherstuff"));
+        let req: Request = serde_json::from_str(req_with_resolutions()).unwrap();
+        assert!(req.api_endpoint().is_none());
+        assert!(req.api_access_token().is_none());
+    }
+
+    #[test]
+    fn test_region() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(req.region(), Some(Region::NorthAmerica));
+        v["context"]["System"]["apiEndpoint"] = JsonValue::from("https://api.fe.amazonalexa.com");
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.region(), Some(Region::FarEast));
+        assert_eq!(
+            Region::from_endpoint("https://api.eu.amazonalexa.com/"),
+            Some(Region::Europe)
+        );
+        assert_eq!(Region::Europe.base_url(), "https://api.eu.amazonalexa.com");
+        assert_eq!(Region::from_endpoint("https://example.com"), None);
+        let req: Request = serde_json::from_str(req_with_resolutions()).unwrap();
+        assert!(req.region().is_none());
+    }
+
+    #[test]
+    fn test_application_id() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert_eq!(req.application_id(), Some("amzn1.ask.skill.myappid"));
+        assert!(req.is_for_application("amzn1.ask.skill.myappid"));
+        assert!(!req.is_for_application("amzn1.ask.skill.otherappid"));
+        // session and context disagree
+        let req: Request = serde_json::from_str(req_with_slots()).unwrap();
+        assert!(!req.is_for_application("amzn1.ask.skill.tehappz"));
+    }
+
+    #[test]
+    fn test_request_id_and_timestamp() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert_eq!(
+            req.request_id(),
+            "amzn1.echo-api.request.b8b49fde-4370-423f-bbb0-dc7305b788a0"
+        );
+        assert_eq!(req.timestamp(), "2018-12-03T00:33:58Z");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_parsed() {
+        use chrono::{Datelike, Timelike};
+
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        let t = req.timestamp_parsed().unwrap();
+        assert_eq!((t.year(), t.month(), t.day()), (2018, 12, 3));
+        assert_eq!((t.hour(), t.minute(), t.second()), (0, 33, 58));
+    }
+
+    #[test]
+    fn test_request_types() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        for (t, expected) in &[
+            ("SessionEndedRequest", RequestType::SessionEndedRequest),
+            ("AudioPlayer.PlaybackStarted", RequestType::PlaybackStarted),
+            (
+                "PlaybackController.PauseCommandIssued",
+                RequestType::PauseCommandIssued,
+            ),
+            ("AlexaSkillEvent.SkillEnabled", RequestType::SkillEnabled),
+            (
+                "Some.FutureRequest",
+                RequestType::Other(String::from("Some.FutureRequest")),
+            ),
+        ] {
+            v["request"]["type"] = JsonValue::from(*t);
+            let req: Request = serde_json::from_value(v.clone()).unwrap();
+            assert_eq!(req.request_type(), *expected);
         }
     }
 
-    #[test]
-    fn test_is_french() {
-        let p: Result<Request, serde_json::Error> =
-            self::serde_json::from_str(default_french_req());
-        match p {
-            Ok(req) => assert!(req.locale().is_french()),
-            Err(e) => panic!(e.to_string()),
-        }
-    }
-    #[test]
-    fn test_intent() {
-        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
-        match p {
-            Ok(req) => assert_eq!(req.intent(), IntentType::User(String::from("hello"))),
-            Err(e) => panic!(e.to_string()),
-        }
+    #[test]
+    fn test_supported_interfaces() {
+        let req: Request = serde_json::from_str(req_with_slots()).unwrap();
+        assert!(req.supports_display());
+        assert!(!req.supports_apl());
+        assert!(!req.supports_audio_player());
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert!(!req.supports_display());
+        assert!(!req.supports_video_app());
+    }
+
+    #[test]
+    fn test_viewport() {
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        let viewport = req.viewport().unwrap();
+        assert_eq!(viewport.shape.as_deref(), Some("RECTANGLE"));
+        assert_eq!(viewport.dpi, Some(160));
+        assert_eq!(viewport.touch, Some(vec![String::from("SINGLE")]));
+        assert_eq!(req.viewport_profile(), ViewportProfile::HubLandscapeMedium);
+        let req: Request = serde_json::from_str(task_req()).unwrap();
+        assert_eq!(req.viewport_profile(), ViewportProfile::Unknown);
+    }
+
+    #[test]
+    fn test_viewports() {
+        let req: Request = serde_json::from_str(viewports_req()).unwrap();
+        let viewports = req.viewports();
+        assert_eq!(viewports.len(), 2);
+        assert!(viewports[0].is_apl());
+        assert_eq!(viewports[0].pixel_size(), Some((1280, 800)));
+        assert!(viewports[1].is_aplt());
+        assert_eq!(viewports[1].line_length, Some(4));
+        assert_eq!(
+            viewports[1].supported_profiles,
+            Some(vec![String::from("FOUR_CHARACTER_CLOCK")])
+        );
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert!(req.viewports().is_empty());
+    }
+
+    #[test]
+    fn test_person_id() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(req.person_id().is_none());
+        v["context"]["System"]["person"] = serde_json::json!({
+            "personId": "amzn1.ask.person.speaker1",
+            "accessToken": "person-token"
+        });
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.person_id(), Some("amzn1.ask.person.speaker1"));
+        assert_eq!(
+            req.context.system.person.unwrap().access_token.as_deref(),
+            Some("person-token")
+        );
+    }
+
+    #[test]
+    fn test_audio_player() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert!(req.audio_player().is_none());
+        v["context"]["AudioPlayer"] = serde_json::json!({
+            "token": "episode-42",
+            "offsetInMilliseconds": 93000,
+            "playerActivity": "PAUSED"
+        });
+        let req: Request = serde_json::from_value(v).unwrap();
+        let player = req.audio_player().unwrap();
+        assert_eq!(player.token(), Some("episode-42"));
+        assert_eq!(player.offset_in_milliseconds(), 93000);
+        assert_eq!(player.player_activity(), PlayerActivity::Paused);
+    }
+
+    #[test]
+    fn test_exception_encountered() {
+        let req: Request = serde_json::from_str(exception_req()).unwrap();
+        assert_eq!(req.request_type(), RequestType::ExceptionEncountered);
+        let error = req.error().unwrap();
+        assert_eq!(error.error_type, "INVALID_RESPONSE");
+        assert_eq!(
+            error.message.as_deref(),
+            Some("Invalid directive type: AudioPlayer.Pley")
+        );
+        assert_eq!(req.cause_request_id(), Some("amzn1.echo-api.request.0"));
+    }
+
+    #[test]
+    fn test_can_fulfill_intent() {
+        let req: Request = serde_json::from_str(can_fulfill_req()).unwrap();
+        assert_eq!(req.request_type(), RequestType::CanFulfillIntentRequest);
+        assert!(req.session.is_none());
+        let intent = req.can_fulfill_intent().unwrap();
+        assert_eq!(intent.name, "FlightIntent");
+        assert_eq!(req.slot_value("destination"), Some("Seattle"));
+        assert!(req.slot_value("date").is_none());
+        let req: Request = serde_json::from_str(default_req()).unwrap();
+        assert!(req.can_fulfill_intent().is_none());
+    }
+
+    #[test]
+    fn test_element_selected() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        v["request"] = serde_json::json!({
+            "type": "Display.ElementSelected",
+            "requestId": "amzn1.echo-api.request.1",
+            "timestamp": "2019-03-01T10:00:00Z",
+            "locale": "en-US",
+            "token": "planet-jupiter"
+        });
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.request_type(), RequestType::ElementSelected);
+        assert_eq!(req.token(), Some("planet-jupiter"));
+    }
+
+    #[test]
+    fn test_user_event() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        v["request"] = serde_json::json!({
+            "type": "Alexa.Presentation.APL.UserEvent",
+            "requestId": "amzn1.echo-api.request.1",
+            "timestamp": "2019-03-01T10:00:00Z",
+            "locale": "en-US",
+            "token": "planets",
+            "arguments": ["next", {"page": 2}],
+            "source": {"type": "TouchWrapper", "handler": "Press", "id": "nextButton"},
+            "components": {"nameInput": "Ann"}
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(req.request_type(), RequestType::UserEvent);
+        assert_eq!(req.token(), Some("planets"));
+        assert_eq!(req.arguments().len(), 2);
+        assert_eq!(req.arguments()[1]["page"], 2);
+        let source = req.user_event_source().unwrap();
+        assert_eq!(source.source_type, "TouchWrapper");
+        assert_eq!(source.handler.as_deref(), Some("Press"));
+        assert_eq!(source.id.as_deref(), Some("nextButton"));
+        assert_eq!(req.component_value("nameInput").unwrap(), "Ann");
+        assert!(req.component_value("other").is_none());
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+    }
+
+    #[test]
+    fn test_load_list_data() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        v["request"] = serde_json::json!({
+            "type": "Alexa.Presentation.APL.LoadIndexListData",
+            "requestId": "amzn1.echo-api.request.1",
+            "timestamp": "2019-03-01T10:00:00Z",
+            "locale": "en-US",
+            "token": "planets",
+            "correlationToken": "c1",
+            "listId": "planetList",
+            "startIndex": 20,
+            "count": 10
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(req.request_type(), RequestType::LoadIndexListData);
+        assert_eq!(req.correlation_token(), Some("c1"));
+        assert_eq!(req.list_id(), Some("planetList"));
+        assert_eq!(req.index_range(), Some((20, 10)));
+        assert!(req.page_token().is_none());
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+
+        v["request"] = serde_json::json!({
+            "type": "Alexa.Presentation.APL.LoadTokenListData",
+            "requestId": "amzn1.echo-api.request.1",
+            "timestamp": "2019-03-01T10:00:00Z",
+            "locale": "en-US",
+            "correlationToken": "c2",
+            "listId": "planetList",
+            "pageToken": "page2"
+        });
+        let req: Request = serde_json::from_value(v).unwrap();
+        assert_eq!(req.request_type(), RequestType::LoadTokenListData);
+        assert_eq!(req.page_token(), Some("page2"));
+        assert!(req.index_range().is_none());
+    }
+
+    #[test]
+    fn test_apl_runtime_error() {
+        let mut v: JsonValue = serde_json::from_str(default_req()).unwrap();
+        assert!(serde_json::from_value::<Request>(v.clone())
+            .unwrap()
+            .apl_errors()
+            .is_empty());
+        v["request"] = serde_json::json!({
+            "type": "Alexa.Presentation.APL.RuntimeError",
+            "requestId": "amzn1.echo-api.request.1",
+            "timestamp": "2019-03-01T10:00:00Z",
+            "locale": "en-US",
+            "token": "planets",
+            "errors": [
+                {
+                    "type": "LIST_ERROR",
+                    "reason": "INVALID_DATASOURCE",
+                    "listId": "planetList",
+                    "message": "Missing items"
+                },
+                {"type": "RENDER_ERROR", "reason": "INTERNAL_ERROR"}
+            ]
+        });
+        let req: Request = serde_json::from_value(v.clone()).unwrap();
+        assert_eq!(req.request_type(), RequestType::RuntimeError);
+        let errors = req.apl_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_type, "LIST_ERROR");
+        assert_eq!(errors[0].reason, "INVALID_DATASOURCE");
+        assert_eq!(errors[0].list_id.as_deref(), Some("planetList"));
+        assert_eq!(errors[0].message.as_deref(), Some("Missing items"));
+        assert!(errors[1].list_id.is_none());
+        assert_eq!(serde_json::to_value(&req).unwrap(), v);
+    }
+
+    #[test]
+    fn test_no_list_event() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => assert!(req.list_event().is_none()),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_smart_properties() {
+        let p: Result<Request, serde_json::Error> =
+            self::serde_json::from_str(smart_properties_req());
+        match p {
+            Ok(req) => {
+                assert_eq!(
+                    req.persistent_endpoint_id(),
+                    Some("amzn1.alexa.endpoint.room101")
+                );
+                assert_eq!(req.unit_id(), Some("amzn1.ask.unit.A1"));
+                assert_eq!(
+                    req.persistent_unit_id(),
+                    Some("amzn1.alexa.unit.did.room101")
+                );
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_no_smart_properties() {
+        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
+        match p {
+            Ok(req) => {
+                assert!(req.persistent_endpoint_id().is_none());
+                assert!(req.unit().is_none());
+                assert!(req.unit_id().is_none());
+                assert!(req.persistent_unit_id().is_none());
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for fixture in &[
+            default_req(),
+            req_with_slots(),
+            default_spanish_req(),
+            default_french_req(),
+            task_req(),
+            list_event_req(),
+            smart_properties_req(),
+            req_with_resolutions(),
+            viewports_req(),
+            exception_req(),
+            can_fulfill_req(),
+            input_handler_req(),
+            custom_events_req(),
+            api_invoked_req(),
+            apl_context_req(),
+        ] {
+            let req: Request = serde_json::from_str(fixture).unwrap();
+            let original: JsonValue = serde_json::from_str(fixture).unwrap();
+            assert_eq!(serde_json::to_value(&req).unwrap(), original);
+        }
+    }
+
+    fn smart_properties_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"user": {
+				"userId": "amzn1.ask.account.theuserid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.roomdevice",
+				"persistentEndpointId": "amzn1.alexa.endpoint.room101",
+				"supportedInterfaces": {}
+			},
+			"unit": {
+				"unitId": "amzn1.ask.unit.A1",
+				"persistentUnitId": "amzn1.alexa.unit.did.room101"
+			},
+			"apiEndpoint": "https://api.amazonalexa.com"
+		}
+	},
+	"request": {
+		"type": "LaunchRequest",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-03-01T10:00:00Z",
+		"locale": "en-US"
+	}
+}"#
+    }
+
+    fn list_event_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"user": {
+				"userId": "amzn1.ask.account.theuserid",
+				"permissions": {
+					"consentToken": "consent.token"
+				}
+			},
+			"apiEndpoint": "https://api.amazonalexa.com",
+			"apiAccessToken": "53kr14t.k3y.d4t4-otherstuff"
+		}
+	},
+	"request": {
+		"type": "AlexaHouseholdListEvent.ItemsCreated",
+		"requestId": "amzn1.echo-api.request.5678",
+		"timestamp": "2018-12-03T00:33:58Z",
+		"eventCreationTime": "2018-12-03T00:33:57Z",
+		"eventPublishingTime": "2018-12-03T00:33:58Z",
+		"body": {
+			"listId": "list-id-1",
+			"listItemIds": [
+				"item-1",
+				"item-2"
+			]
+		}
+	}
+}"#
+    }
+
+    fn input_handler_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"session": {
+		"new": false,
+		"sessionId": "amzn1.echo-api.session.abc123",
+		"application": {
+			"applicationId": "amzn1.ask.skill.myappid"
+		},
+		"user": {
+			"userId": "amzn1.ask.account.theuserid"
+		}
+	},
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.superfakedevice",
+				"supportedInterfaces": {}
+			}
+		}
+	},
+	"request": {
+		"type": "GameEngine.InputHandlerEvent",
+		"requestId": "amzn1.echo-api.request.5678",
+		"timestamp": "2018-12-03T00:33:58Z",
+		"locale": "en-US",
+		"originatingRequestId": "amzn1.echo-api.request.1234",
+		"events": [
+			{
+				"name": "button_down_event",
+				"inputEvents": [
+					{
+						"gadgetId": "amzn1.ask.gadget.05RPH7PJG9C61DHI4QR0RLOQOHKGVQ",
+						"timestamp": "2018-12-03T00:33:57.123Z",
+						"color": "FF0000",
+						"feature": "press",
+						"action": "down"
+					}
+				]
+			}
+		]
+	}
+}"#
+    }
+
+    fn apl_context_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.superfakedevice",
+				"supportedInterfaces": {
+					"Alexa.Presentation.APL": {
+						"runtime": {
+							"maxVersion": "1.4"
+						}
+					}
+				}
+			}
+		},
+		"Alexa.Presentation.APL": {
+			"token": "planetList",
+			"version": "AriaPlayer-1.4",
+			"componentsVisibleOnScreen": [
+				{
+					"uid": ":1000",
+					"position": "1024x600+0+0:0",
+					"type": "mixed",
+					"tags": {
+						"viewport": {}
+					},
+					"children": [
+						{
+							"uid": ":1001",
+							"id": "jupiter",
+							"position": "1024x100+0+0:1",
+							"type": "text",
+							"tags": {
+								"ordinal": 1
+							}
+						},
+						{
+							"uid": ":1002",
+							"id": "mars",
+							"position": "1024x100+0+100:1",
+							"type": "text",
+							"tags": {
+								"ordinal": 2
+							}
+						},
+						{
+							"uid": ":1003",
+							"id": "saturn",
+							"position": "1024x100+0+200:1",
+							"type": "text",
+							"tags": {
+								"ordinal": 3
+							},
+							"entities": [
+								{
+									"type": "planet",
+									"value": "Saturn"
+								}
+							]
+						}
+					]
+				}
+			]
+		}
+	},
+	"request": {
+		"type": "IntentRequest",
+		"requestId": "amzn1.echo-api.request.5678",
+		"timestamp": "2018-12-03T00:33:58Z",
+		"locale": "en-US",
+		"intent": {
+			"name": "SelectIntent",
+			"confirmationStatus": "NONE"
+		}
+	}
+}"#
     }
 
-    #[test]
-    fn test_slot() {
-        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(req_with_slots());
-        match p {
-            Ok(req) => assert_eq!(req.slot_value("name"), Some(String::from("bob"))),
-            Err(e) => panic!(e.to_string()),
-        }
+    fn api_invoked_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"session": {
+		"new": false,
+		"sessionId": "amzn1.echo-api.session.abc123",
+		"application": {
+			"applicationId": "amzn1.ask.skill.myappid"
+		},
+		"user": {
+			"userId": "amzn1.ask.account.theuserid"
+		}
+	},
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			}
+		}
+	},
+	"request": {
+		"type": "Dialog.API.Invoked",
+		"requestId": "amzn1.echo-api.request.5678",
+		"timestamp": "2018-12-03T00:33:58Z",
+		"locale": "en-US",
+		"apiRequest": {
+			"name": "GetWeather",
+			"arguments": {
+				"cityName": "Seattle",
+				"days": 3
+			},
+			"slots": {
+				"cityName": {
+					"type": "Simple",
+					"value": "seattle"
+				}
+			}
+		}
+	}
+}"#
     }
 
-    #[test]
-    fn test_attribute() {
-        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
-        match p {
-            Ok(req) => {
-                assert!(req.session.is_some());
-                assert!(req.session.unwrap().attributes.is_some());
-            }
-            Err(e) => panic!(e.to_string()),
-        }
+    fn custom_events_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.superfakedevice",
+				"supportedInterfaces": {}
+			}
+		}
+	},
+	"request": {
+		"type": "CustomInterfaceController.EventsReceived",
+		"requestId": "amzn1.echo-api.request.5678",
+		"timestamp": "2018-12-03T00:33:58Z",
+		"locale": "en-US",
+		"token": "1234abcd-40bb-11e9-9527-6b98b093d166",
+		"events": [
+			{
+				"header": {
+					"namespace": "Custom.Robot",
+					"name": "EyeBlink"
+				},
+				"endpoint": {
+					"endpointId": "amzn1.ask.endpoint.ABCD"
+				},
+				"payload": {
+					"ack": "ok"
+				}
+			}
+		]
+	}
+}"#
     }
 
-    #[test]
-    fn test_attribute_val() {
-        let p: Result<Request, serde_json::Error> = self::serde_json::from_str(default_req());
-        match p {
-            Ok(req) => assert_eq!(
-                req.attribute_value("lastSpeech"),
-                Some(&String::from(
-                    "Jupiter has the shortest day of all the planets"
-                ))
-            ),
-            Err(e) => panic!(e.to_string()),
-        }
+    fn task_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"session": {
+		"new": true,
+		"sessionId": "amzn1.echo-api.session.abc123",
+		"application": {
+			"applicationId": "amzn1.ask.skill.myappid"
+		},
+		"user": {
+			"userId": "amzn1.ask.account.theuserid"
+		}
+	},
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"user": {
+				"userId": "amzn1.ask.account.theuserid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.superfakedevice",
+				"supportedInterfaces": {}
+			},
+			"apiEndpoint": "https://api.amazonalexa.com",
+			"apiAccessToken": "53kr14t.k3y.d4t4-otherstuff"
+		}
+	},
+	"request": {
+		"type": "LaunchRequest",
+		"requestId": "amzn1.echo-api.request.1234",
+		"timestamp": "2020-03-22T17:24:44Z",
+		"locale": "en-US",
+		"task": {
+			"name": "AMZN1.ask.skill.myappid.PlaySoundTask",
+			"version": "1",
+			"input": {
+				"sound": "rain"
+			}
+		}
+	}
+}"#
     }
 
     fn default_spanish_req() -> &'static str {
@@ -622,6 +3398,225 @@ mod tests {
 }"#
     }
 
+    fn can_fulfill_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"user": {
+				"userId": "amzn1.ask.account.theuserid"
+			},
+			"device": {
+				"supportedInterfaces": {},
+				"deviceId": "amzn1.ask.device.1"
+			},
+			"apiEndpoint": "https://api.amazonalexa.com"
+		}
+	},
+	"request": {
+		"type": "CanFulfillIntentRequest",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-03-01T10:00:00Z",
+		"locale": "en-US",
+		"intent": {
+			"name": "FlightIntent",
+			"slots": {
+				"destination": {
+					"name": "destination",
+					"value": "Seattle"
+				},
+				"date": {
+					"name": "date"
+				}
+			}
+		}
+	}
+}"#
+    }
+
+    fn exception_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"user": {
+				"userId": "amzn1.ask.account.theuserid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.1",
+				"supportedInterfaces": {
+					"AudioPlayer": {}
+				}
+			}
+		}
+	},
+	"request": {
+		"type": "System.ExceptionEncountered",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-03-01T10:00:00Z",
+		"locale": "en-US",
+		"error": {
+			"type": "INVALID_RESPONSE",
+			"message": "Invalid directive type: AudioPlayer.Pley"
+		},
+		"cause": {
+			"requestId": "amzn1.echo-api.request.0"
+		}
+	}
+}"#
+    }
+
+    fn viewports_req() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			},
+			"device": {
+				"deviceId": "amzn1.ask.device.1",
+				"supportedInterfaces": {
+					"Alexa.Presentation.APL": {
+						"runtime": {
+							"maxVersion": "1.4"
+						}
+					},
+					"Alexa.Presentation.APLT": {
+						"runtime": {
+							"maxVersion": "1.0"
+						}
+					}
+				}
+			}
+		},
+		"Viewports": [
+			{
+				"type": "APL",
+				"id": "main",
+				"shape": "RECTANGLE",
+				"dpi": 160,
+				"presentationType": "STANDARD",
+				"canRotate": false,
+				"configuration": {
+					"current": {
+						"mode": "HUB",
+						"video": {
+							"codecs": [
+								"H_264_42"
+							]
+						},
+						"size": {
+							"type": "DISCRETE",
+							"pixelWidth": 1280,
+							"pixelHeight": 800
+						}
+					}
+				}
+			},
+			{
+				"type": "APLT",
+				"id": "clock",
+				"supportedProfiles": [
+					"FOUR_CHARACTER_CLOCK"
+				],
+				"lineLength": 4,
+				"lineCount": 1,
+				"format": "SEVEN_SEGMENT",
+				"interSegments": [
+					{
+						"x": 2,
+						"y": 0,
+						"characters": "':."
+					}
+				]
+			}
+		]
+	},
+	"request": {
+		"type": "LaunchRequest",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-03-01T10:00:00Z",
+		"locale": "en-US"
+	}
+}"#
+    }
+
+    fn req_with_resolutions() -> &'static str {
+        r#"{
+	"version": "1.0",
+	"context": {
+		"System": {
+			"application": {
+				"applicationId": "amzn1.ask.skill.myappid"
+			}
+		}
+	},
+	"request": {
+		"type": "IntentRequest",
+		"requestId": "amzn1.echo-api.request.1",
+		"timestamp": "2019-03-01T10:00:00Z",
+		"locale": "en-US",
+		"intent": {
+			"name": "PlanetIntent",
+			"confirmationStatus": "NONE",
+			"slots": {
+				"planet": {
+					"name": "planet",
+					"value": "the gas giant",
+					"confirmationStatus": "NONE",
+					"resolutions": {
+						"resolutionsPerAuthority": [
+							{
+								"authority": "amzn1.er-authority.echo-sdk.dynamic",
+								"status": {
+									"code": "ER_SUCCESS_NO_MATCH"
+								}
+							},
+							{
+								"authority": "amzn1.er-authority.echo-sdk.amzn1.ask.skill.myappid.Planet",
+								"status": {
+									"code": "ER_SUCCESS_MATCH"
+								},
+								"values": [
+									{
+										"value": {
+											"name": "Jupiter",
+											"id": "JUPITER"
+										}
+									}
+								]
+							}
+						]
+					}
+				},
+				"moon": {
+					"name": "moon",
+					"value": "phobos",
+					"confirmationStatus": "NONE",
+					"resolutions": {
+						"resolutionsPerAuthority": [
+							{
+								"authority": "amzn1.er-authority.echo-sdk.amzn1.ask.skill.myappid.Moon",
+								"status": {
+									"code": "ER_SUCCESS_NO_MATCH"
+								}
+							}
+						]
+					}
+				}
+			}
+		}
+	}
+}"#
+    }
+
     fn req_with_slots() -> &'static str {
         r#"{
 	"version": "1.0",
@@ -3,34 +3,85 @@ extern crate serde_derive;
 extern crate serde_json;
 
 use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value;
+use super::apl::{
+    ApltRenderDocument, ExecuteCommands, RenderDocument, SendIndexListData, SendTokenListData,
+    UpdateIndexListData,
+};
+use super::can_fulfill::CanFulfillIntent;
+use super::dialog::{ConfirmIntent, ConfirmSlot, DelegateRequest, ElicitSlot};
+use super::display::{Hint, RenderTemplate, Template};
+use super::entities::UpdateDynamicEntities;
+use super::error::Error;
+use super::gadgets::{StartEventHandler, StartInputHandler};
+use super::html::{HandleMessage, Start};
+use super::purchasing::SendRequest;
+use super::request::{Locale, Request};
+use super::video::Launch;
 use std::collections::HashMap;
 use std::fmt;
 
-enum Version {
+/// Response spec versions
+/// versions not known to this crate are put into the Other `String` value;
+/// the response body is serialized the same way for every version, only the
+/// `version` field differs
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Version {
+    #[default]
     V1_0,
+    Other(String),
 }
 
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = match *self {
+        let s = match self {
             Version::V1_0 => "1.0",
+            Version::Other(v) => v,
         };
         write!(f, "{}", s)
     }
 }
 
+impl<'a> From<&'a str> for Version {
+    fn from(s: &'a str) -> Version {
+        match s {
+            "1.0" => Version::V1_0,
+            _ => Version::Other(String::from(s)),
+        }
+    }
+}
+
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Version, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Version::from(s.as_str()))
+    }
+}
+
 impl Response {
     /// Constructs a new response with only required elements
     pub fn new(should_end: bool) -> Response {
         Response {
-            version: Version::V1_0.to_string(),
+            version: Version::default(),
             session_attributes: None,
             body: ResBody {
                 output_speech: None,
                 card: None,
                 reprompt: None,
-                should_end_session: should_end,
+                directives: None,
+                can_fulfill_intent: None,
+                api_response: None,
+                experimentation: None,
+                should_end_session: Some(should_end),
+                extra: HashMap::new(),
             },
+            extra: HashMap::new(),
         }
     }
 
@@ -46,11 +97,46 @@ impl Response {
             .speech(Speech::plain(text))
     }
 
+    /// Constructs a plain response asking a question, keeping the session
+    /// open and reprompting if the user doesn't answer
+    pub fn ask(speech: &str, reprompt: &str) -> Response {
+        Response::new(false)
+            .speech(Speech::plain(speech))
+            .reprompt(Speech::plain(reprompt))
+    }
+
     /// Constructs an empty response ending the session
     pub fn end() -> Response {
         Response::new(true)
     }
 
+    /// Constructs a response completing a task (e.g. from a quick link) with
+    /// the given status code ("200" for success, "400"/"500" for failures)
+    pub fn complete_task(code: &str, message: &str) -> Response {
+        let mut res = Response::new(true);
+        res.add_directive(Directive::complete_task(code, message));
+        res
+    }
+
+    /// Constructs a response asking the user for the named slot with the
+    /// given speech instead of the interaction model prompt
+    pub fn elicit_slot(slot: &str, speech: &str) -> Response {
+        Response::new(false)
+            .speech(Speech::plain(speech))
+            .directive(Directive::ElicitSlot(ElicitSlot::new(slot)))
+    }
+
+    /// targets the given response spec version instead of the default
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// the response spec version targeted by the response
+    pub fn spec_version(&self) -> Version {
+        self.version.clone()
+    }
+
     /// adds a speach element to the response
     pub fn speech(mut self, speech: Speech) -> Self {
         self.body.output_speech = Some(speech);
@@ -63,32 +149,254 @@ impl Response {
         self
     }
 
+    /// sets whether the session ends after this response
+    pub fn end_session(mut self, should_end: bool) -> Self {
+        self.body.should_end_session = Some(should_end);
+        self
+    }
+
+    /// sets whether the session ends after this response, same as
+    /// `end_session`
+    pub fn should_end_session(self, should_end: bool) -> Self {
+        self.end_session(should_end)
+    }
+
+    /// adds a reprompt, spoken if the user doesn't answer while the session
+    /// is open
+    pub fn reprompt(mut self, speech: Speech) -> Self {
+        self.body.reprompt = Some(Reprompt {
+            output_speech: speech,
+        });
+        self
+    }
+
+    /// answers a CanFulfillIntentRequest
+    pub fn can_fulfill(mut self, can_fulfill: CanFulfillIntent) -> Self {
+        self.body.can_fulfill_intent = Some(can_fulfill);
+        self
+    }
+
+    /// sets the result of an Alexa Conversations API, answering a
+    /// Dialog.API.Invoked request
+    pub fn api_response(mut self, payload: Value) -> Self {
+        self.body.api_response = Some(payload);
+        self
+    }
+
+    /// reports that the experiment with the given id was triggered, i.e. the
+    /// response took the path being tested
+    pub fn trigger_experiment(mut self, experiment_id: &str) -> Self {
+        self.body
+            .experimentation
+            .get_or_insert_with(Experimentation::default)
+            .triggered_experiments
+            .push(String::from(experiment_id));
+        self
+    }
+
+    /// adds a generic reprompt in the given locale when the session stays
+    /// open without one, which certification otherwise rejects
+    pub fn default_reprompt(self, locale: &Locale) -> Self {
+        if self.body.should_end_session != Some(false) || self.body.reprompt.is_some() {
+            return self;
+        }
+        self.reprompt(Speech::plain(default_reprompt_text(locale)))
+    }
+
     /// adds an attribute key/value pair to the response
     /// attributes can be read on the next request for basic state
     /// persistance
     pub fn add_attribute(&mut self, key: &str, val: &str) {
-        if let Some(ref mut h) = self.session_attributes {
-            let _ = h.insert(String::from(key), String::from(val));
+        self.insert_attribute(key, Value::from(val));
+    }
+
+    /// adds an attribute holding any serializable value, e.g. a counter or
+    /// a struct, to be read back with `Request::attr`
+    pub fn set_attr<T: serde::Serialize>(&mut self, key: &str, val: &T) -> Result<(), Error> {
+        let v = serde_json::to_value(val).map_err(Error::Response)?;
+        self.insert_attribute(key, v);
+        Ok(())
+    }
+
+    /// adds an attribute key/value pair, chainable variant of `add_attribute`
+    pub fn attribute(mut self, key: &str, val: &str) -> Self {
+        self.add_attribute(key, val);
+        self
+    }
+
+    /// removes an attribute so it is not carried into the next request
+    pub fn remove_attribute(&mut self, key: &str) {
+        if let Some(ref mut a) = self.session_attributes {
+            a.remove(key);
+        }
+    }
+
+    fn insert_attribute(&mut self, key: &str, val: Value) {
+        self.session_attributes
+            .get_or_insert_with(HashMap::new)
+            .insert(String::from(key), val);
+    }
+
+    // VideoApp.Launch responses must not include shouldEndSession
+    pub(crate) fn omit_end_session(mut self) -> Self {
+        self.body.should_end_session = None;
+        self
+    }
+
+    /// adds a directive to the response
+    pub fn add_directive(&mut self, directive: Directive) {
+        if let Some(ref mut d) = self.body.directives {
+            d.push(directive);
         } else {
-            let mut h = HashMap::new();
-            h.insert(String::from(key), String::from(val));
-            self.session_attributes = Some(h)
+            self.body.directives = Some(vec![directive]);
+        }
+    }
+
+    /// adds a directive, chainable variant of `add_directive`
+    pub fn directive(mut self, directive: Directive) -> Self {
+        self.add_directive(directive);
+        self
+    }
+
+    /// adds a directive only if the requesting device supports its interface,
+    /// returning whether it was added
+    pub fn add_supported_directive(&mut self, req: &Request, directive: Directive) -> bool {
+        if let Some(interface) = directive.interface() {
+            if !req.supports_interface(interface) {
+                return false;
+            }
+        }
+        self.add_directive(directive);
+        true
+    }
+
+    /// the response body, to inspect speech, card, reprompt and directives
+    pub fn body(&self) -> &ResBody {
+        &self.body
+    }
+
+    /// the session attributes set on the response, if any
+    pub fn attributes(&self) -> Option<&HashMap<String, Value>> {
+        self.session_attributes.as_ref()
+    }
+
+    /// serializes the response to JSON
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string(self).map_err(Error::Response)
+    }
+
+    /// lists the field-level differences between this response and `other`,
+    /// e.g. to catch behavior changes in regression tests
+    pub fn diff(&self, other: &Response) -> Vec<Difference> {
+        let mut diffs = Vec::new();
+        let a = serde_json::to_value(self).unwrap_or(Value::Null);
+        let b = serde_json::to_value(other).unwrap_or(Value::Null);
+        diff_values("", &a, &b, &mut diffs);
+        diffs
+    }
+}
+
+fn default_reprompt_text(locale: &Locale) -> &'static str {
+    if locale.is_french() {
+        return "Puis-je vous aider avec autre chose ?";
+    }
+    if locale.is_spanish() {
+        return "¿Hay algo más en lo que pueda ayudarte?";
+    }
+    match *locale {
+        Locale::German => "Kann ich dir sonst noch helfen?",
+        Locale::Italian => "Posso aiutarti con qualcos'altro?",
+        Locale::Japanese => "他に何かお手伝いできることはありますか？",
+        Locale::Hindi => "क्या मैं आपकी और कोई मदद कर सकती हूँ?",
+        Locale::BrazilianPortuguese => "Posso ajudar com mais alguma coisa?",
+        Locale::Arabic => "هل هناك أي شيء آخر يمكنني مساعدتك به؟",
+        Locale::Dutch => "Kan ik je nog ergens anders mee helpen?",
+        _ => "Is there anything else I can help you with?",
+    }
+}
+
+/// A single difference between two responses, keyed by the JSON path of the
+/// field (e.g. `response.outputSpeech.ssml` or `response.directives[1]`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    Added(String, Value),
+    Removed(String, Value),
+    Changed(String, Value, Value),
+}
+
+impl Difference {
+    /// the JSON path of the differing field
+    pub fn path(&self) -> &str {
+        match self {
+            Difference::Added(p, _) | Difference::Removed(p, _) | Difference::Changed(p, _, _) => p,
+        }
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        String::from(key)
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn diff_values(path: &str, a: &Value, b: &Value, diffs: &mut Vec<Difference>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, va) in a {
+                let p = join_path(path, k);
+                match b.get(k) {
+                    Some(vb) => diff_values(&p, va, vb, diffs),
+                    None => diffs.push(Difference::Removed(p, va.clone())),
+                }
+            }
+            for (k, vb) in b {
+                if !a.contains_key(k) {
+                    diffs.push(Difference::Added(join_path(path, k), vb.clone()));
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let p = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(va), Some(vb)) => diff_values(&p, va, vb, diffs),
+                    (Some(va), None) => diffs.push(Difference::Removed(p, va.clone())),
+                    (None, Some(vb)) => diffs.push(Difference::Added(p, vb.clone())),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => {
+            if a != b {
+                diffs.push(Difference::Changed(
+                    String::from(path),
+                    a.clone(),
+                    b.clone(),
+                ));
+            }
         }
     }
 }
 
 /// Response struct implementing the [Alexa JSON spec](https://developer.amazon.com/docs/custom-skills/request-and-response-json-reference.html#response-parameters)
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Response {
-    version: String,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    version: Version,
     #[serde(rename = "sessionAttributes")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    session_attributes: Option<HashMap<String, String>>,
+    session_attributes: Option<HashMap<String, Value>>,
     #[serde(rename = "response")]
     body: ResBody,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResBody {
     #[serde(rename = "outputSpeech")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -97,8 +405,76 @@ pub struct ResBody {
     card: Option<Card>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reprompt: Option<Reprompt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    directives: Option<Vec<Directive>>,
+    #[serde(rename = "canFulfillIntent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    can_fulfill_intent: Option<CanFulfillIntent>,
+    #[serde(rename = "apiResponse")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_response: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    experimentation: Option<Experimentation>,
     #[serde(rename = "shouldEndSession")]
-    should_end_session: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    should_end_session: Option<bool>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+impl ResBody {
+    /// the output speech, if any
+    pub fn speech(&self) -> Option<&Speech> {
+        self.output_speech.as_ref()
+    }
+
+    /// the card, if any
+    pub fn card(&self) -> Option<&Card> {
+        self.card.as_ref()
+    }
+
+    /// the reprompt speech, if any
+    pub fn reprompt(&self) -> Option<&Speech> {
+        self.reprompt.as_ref().map(|r| &r.output_speech)
+    }
+
+    /// the directives, empty if there are none
+    pub fn directives(&self) -> &[Directive] {
+        self.directives.as_deref().unwrap_or(&[])
+    }
+
+    /// the answer to a CanFulfillIntentRequest, if any
+    pub fn can_fulfill_intent(&self) -> Option<&CanFulfillIntent> {
+        self.can_fulfill_intent.as_ref()
+    }
+
+    /// the Alexa Conversations API result, if any
+    pub fn api_response(&self) -> Option<&Value> {
+        self.api_response.as_ref()
+    }
+
+    /// ids of the experiments reported as triggered
+    pub fn triggered_experiments(&self) -> &[String] {
+        self.experimentation
+            .as_ref()
+            .map(|e| e.triggered_experiments.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// whether the session ends after the response, `None` if omitted
+    /// (as required with VideoApp.Launch)
+    pub fn should_end_session(&self) -> Option<bool> {
+        self.should_end_session
+    }
+}
+
+/// Experiment trigger events reported by the response
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Experimentation {
+    #[serde(rename = "triggeredExperiments")]
+    #[serde(default)]
+    triggered_experiments: Vec<String>,
 }
 
 enum SpeechType {
@@ -135,6 +511,7 @@ impl fmt::Display for PlayBehavior {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Speech {
     #[serde(rename = "type")]
     speech_type: String,
@@ -168,6 +545,11 @@ impl Speech {
         }
     }
 
+    /// the plain text, or the SSML markup of SSML speech
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref().or(self.ssml.as_deref())
+    }
+
     /// Adds play behavior to a speech object
     pub fn play_behavior(&mut self, behavior: PlayBehavior) {
         self.play_behavior = Some(behavior.to_string());
@@ -196,6 +578,7 @@ impl fmt::Display for CardType {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Card {
     #[serde(rename = "type")]
     card_type: String,
@@ -259,15 +642,27 @@ impl Card {
             permissions: Some(permissions),
         }
     }
+
+    /// the card title, if any
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// the card content of simple cards, or the text of standard cards
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref().or(self.text.as_deref())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Reprompt {
     #[serde(rename = "outputSpeech")]
     output_speech: Speech,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Image {
     #[serde(rename = "smallImageUrl")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -293,23 +688,655 @@ impl Image {
     }
 }
 
-impl Default for Image {
-    fn default() -> Self {
-        Image {
-            small_image_url: None,
-            large_image_url: None,
+/// Directives that can be attached to a response
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum Directive {
+    #[serde(rename = "Connections.SendRequest")]
+    SendRequest(SendRequest),
+    #[serde(rename = "Tasks.CompleteTask")]
+    CompleteTask(CompleteTask),
+    #[serde(rename = "GameEngine.StartInputHandler")]
+    StartInputHandler(StartInputHandler),
+    #[serde(rename = "CustomInterfaceController.StartEventHandler")]
+    StartEventHandler(StartEventHandler),
+    #[serde(rename = "Dialog.UpdateDynamicEntities")]
+    UpdateDynamicEntities(UpdateDynamicEntities),
+    #[serde(rename = "Dialog.ElicitSlot")]
+    ElicitSlot(ElicitSlot),
+    #[serde(rename = "Dialog.ConfirmSlot")]
+    ConfirmSlot(ConfirmSlot),
+    #[serde(rename = "Dialog.ConfirmIntent")]
+    ConfirmIntent(ConfirmIntent),
+    #[serde(rename = "Dialog.DelegateRequest")]
+    DelegateRequest(DelegateRequest),
+    #[serde(rename = "Alexa.Presentation.APL.RenderDocument")]
+    RenderDocument(RenderDocument),
+    #[serde(rename = "Alexa.Presentation.APL.ExecuteCommands")]
+    ExecuteCommands(ExecuteCommands),
+    /// APL for Audio document, supported on all devices
+    #[serde(rename = "Alexa.Presentation.APLA.RenderDocument")]
+    AplaRenderDocument(RenderDocument),
+    #[serde(rename = "Alexa.Presentation.APLT.RenderDocument")]
+    ApltRenderDocument(ApltRenderDocument),
+    #[serde(rename = "Alexa.Presentation.APL.SendIndexListData")]
+    SendIndexListData(SendIndexListData),
+    #[serde(rename = "Alexa.Presentation.APL.UpdateIndexListData")]
+    UpdateIndexListData(UpdateIndexListData),
+    #[serde(rename = "Alexa.Presentation.APL.SendTokenListData")]
+    SendTokenListData(SendTokenListData),
+    #[serde(rename = "Alexa.Presentation.HTML.Start")]
+    HtmlStart(Start),
+    #[serde(rename = "Alexa.Presentation.HTML.HandleMessage")]
+    HandleMessage(HandleMessage),
+    #[serde(rename = "Hint")]
+    Hint(Hint),
+    #[serde(rename = "Display.RenderTemplate")]
+    RenderTemplate(RenderTemplate),
+    #[serde(rename = "VideoApp.Launch")]
+    VideoAppLaunch(Launch),
+    /// any directive not modeled by this crate, kept as raw JSON
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl Directive {
+    /// Constructs a Hint directive with the given plain text
+    pub fn hint(text: &str) -> Directive {
+        Directive::Hint(Hint::new(text))
+    }
+
+    /// Constructs a Display.RenderTemplate directive showing the template
+    pub fn render_template(template: Template) -> Directive {
+        Directive::RenderTemplate(RenderTemplate::new(template))
+    }
+
+    /// Constructs a Tasks.CompleteTask directive with the given status
+    pub fn complete_task(code: &str, message: &str) -> Directive {
+        Directive::CompleteTask(CompleteTask::new(code, message))
+    }
+
+    /// the interface a device must support for the directive to be sent to
+    /// it, if any
+    pub fn interface(&self) -> Option<&'static str> {
+        match self {
+            Directive::StartInputHandler(_) => Some("GameEngine"),
+            Directive::RenderDocument(_)
+            | Directive::ExecuteCommands(_)
+            | Directive::SendIndexListData(_)
+            | Directive::UpdateIndexListData(_)
+            | Directive::SendTokenListData(_) => Some("Alexa.Presentation.APL"),
+            Directive::ApltRenderDocument(_) => Some("Alexa.Presentation.APLT"),
+            Directive::HtmlStart(_) | Directive::HandleMessage(_) => {
+                Some("Alexa.Presentation.HTML")
+            }
+            Directive::Hint(_) | Directive::RenderTemplate(_) => Some("Display"),
+            Directive::VideoAppLaunch(_) => Some("VideoApp"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CompleteTask {
+    status: TaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+}
+
+impl CompleteTask {
+    /// Constructs a task completion with the given status code and message
+    pub fn new(code: &str, message: &str) -> CompleteTask {
+        CompleteTask {
+            status: TaskStatus {
+                code: String::from(code),
+                message: Some(String::from(message)),
+            },
+            result: None,
         }
     }
+
+    /// sets the (task specific) result payload returned to the requester
+    pub fn result(mut self, result: Value) -> Self {
+        self.result = Some(result);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TaskStatus {
+    code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+/// Constructs an SSML `Speech`, formatting its arguments like `format!`
+#[macro_export]
+macro_rules! ssml {
+    ($($arg:tt)*) => {
+        $crate::response::Speech::ssml(&format!($($arg)*))
+    };
+}
+
+/// Declaratively builds a `Response`. Every field is optional, and the
+/// session ends unless `end_session: false` is given.
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate alexa_sdk;
+///
+/// use alexa_sdk::response::{Card, Directive, Speech};
+///
+/// fn main() {
+///     let planet = "Jupiter";
+///     let _res = alexa_response! {
+///         speech: ssml!("<speak>{} has the shortest day</speak>", planet),
+///         card: Card::simple("Planets", planet),
+///         reprompt: Speech::plain("Want another fact?"),
+///         end_session: false,
+///         attributes: { "lastPlanet" => planet },
+///         directives: [Directive::complete_task("200", "done")],
+///     };
+/// }
+/// ```
+///
+/// A `reprompt` is only valid while the session stays open, so it does not
+/// compile without `end_session: false`:
+///
+/// ```rust,compile_fail
+/// #[macro_use]
+/// extern crate alexa_sdk;
+///
+/// use alexa_sdk::response::Speech;
+///
+/// fn main() {
+///     let _res = alexa_response! {
+///         speech: Speech::plain("goodbye"),
+///         reprompt: Speech::plain("still there?"),
+///     };
+/// }
+/// ```
+///
+/// and neither does an unknown key:
+///
+/// ```rust,compile_fail
+/// #[macro_use]
+/// extern crate alexa_sdk;
+///
+/// use alexa_sdk::response::Speech;
+///
+/// fn main() {
+///     let _res = alexa_response! {
+///         speach: Speech::plain("hello"),
+///     };
+/// }
+/// ```
+#[macro_export]
+macro_rules! alexa_response {
+    (@check $open:ident $reprompt:ident) => {
+        $crate::alexa_response!(@verdict $open $reprompt);
+    };
+    (@check $open:ident $reprompt:ident end_session: false $(, $($rest:tt)*)?) => {
+        $crate::alexa_response!(@check open $reprompt $($($rest)*)?);
+    };
+    (@check $open:ident $reprompt:ident reprompt: $v:expr $(, $($rest:tt)*)?) => {
+        $crate::alexa_response!(@check $open reprompt $($($rest)*)?);
+    };
+    (@check $open:ident $reprompt:ident attributes: { $($a:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::alexa_response!(@check $open $reprompt $($($rest)*)?);
+    };
+    (@check $open:ident $reprompt:ident directives: [ $($d:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::alexa_response!(@check $open $reprompt $($($rest)*)?);
+    };
+    (@check $open:ident $reprompt:ident $k:ident: $v:expr $(, $($rest:tt)*)?) => {
+        $crate::alexa_response!(@check $open $reprompt $($($rest)*)?);
+    };
+    (@check $($rest:tt)*) => {};
+    (@verdict closed reprompt) => {
+        compile_error!("alexa_response!: reprompt requires end_session: false");
+    };
+    (@verdict $open:ident $reprompt:ident) => {};
+    (@apply $res:ident,) => {};
+    (@apply $res:ident, speech: $v:expr $(, $($rest:tt)*)?) => {
+        $res = $res.speech($v);
+        $crate::alexa_response!(@apply $res, $($($rest)*)?);
+    };
+    (@apply $res:ident, card: $v:expr $(, $($rest:tt)*)?) => {
+        $res = $res.card($v);
+        $crate::alexa_response!(@apply $res, $($($rest)*)?);
+    };
+    (@apply $res:ident, reprompt: $v:expr $(, $($rest:tt)*)?) => {
+        $res = $res.reprompt($v);
+        $crate::alexa_response!(@apply $res, $($($rest)*)?);
+    };
+    (@apply $res:ident, end_session: $v:expr $(, $($rest:tt)*)?) => {
+        $res = $res.end_session($v);
+        $crate::alexa_response!(@apply $res, $($($rest)*)?);
+    };
+    (@apply $res:ident, attributes: { $($k:expr => $v:expr),* $(,)? } $(, $($rest:tt)*)?) => {
+        $($res.add_attribute($k, $v);)*
+        $crate::alexa_response!(@apply $res, $($($rest)*)?);
+    };
+    (@apply $res:ident, directives: [ $($d:expr),* $(,)? ] $(, $($rest:tt)*)?) => {
+        $($res.add_directive($d);)*
+        $crate::alexa_response!(@apply $res, $($($rest)*)?);
+    };
+    (@apply $res:ident, $k:ident: $($rest:tt)*) => {
+        compile_error!(concat!("alexa_response!: unknown key `", stringify!($k), "`"));
+    };
+    (@apply $($rest:tt)*) => {
+        compile_error!("alexa_response!: expected `key: value` fields");
+    };
+    ($($body:tt)*) => {{
+        $crate::alexa_response!(@check closed none $($body)*);
+        #[allow(unused_mut)]
+        let mut res = $crate::response::Response::new(true);
+        $crate::alexa_response!(@apply res, $($body)*);
+        res
+    }};
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::apl::{Command, Document, TargetProfile};
+    use crate::can_fulfill::CanFulfill;
+    use crate::display::TemplateType;
+    use crate::video::Launch;
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(Response)).unwrap();
+        assert!(schema["properties"].get("sessionAttributes").is_some());
+        assert!(schema["definitions"].get("Directive").is_some());
+    }
+
+    #[test]
+    fn test_ask() {
+        let v = serde_json::to_value(Response::ask("which planet?", "tell me a planet")).unwrap();
+        assert_eq!(v["response"]["outputSpeech"]["text"], "which planet?");
+        assert_eq!(
+            v["response"]["reprompt"]["outputSpeech"]["text"],
+            "tell me a planet"
+        );
+        assert_eq!(v["response"]["shouldEndSession"], false);
+        assert!(v["response"].get("card").is_none());
+    }
+
+    #[test]
+    fn test_chained_builders() {
+        let res = Response::new(true)
+            .speech(Speech::plain("hello"))
+            .directive(Directive::complete_task("200", "done"))
+            .attribute("step", "greet")
+            .should_end_session(false);
+        let v = serde_json::to_value(res).unwrap();
+        assert_eq!(v["response"]["shouldEndSession"], false);
+        assert_eq!(v["response"]["directives"][0]["type"], "Tasks.CompleteTask");
+        assert_eq!(v["sessionAttributes"]["step"], "greet");
+    }
+
+    #[test]
+    fn test_accessors() {
+        let mut res = Response::ask("which planet?", "tell me a planet")
+            .card(Card::simple("Planets", "Pick a planet"));
+        res.add_attribute("step", "pick");
+        let body = res.body();
+        assert_eq!(body.speech().and_then(|s| s.text()), Some("which planet?"));
+        assert_eq!(
+            body.reprompt().and_then(|s| s.text()),
+            Some("tell me a planet")
+        );
+        assert_eq!(body.card().and_then(|c| c.title()), Some("Planets"));
+        assert_eq!(body.card().and_then(|c| c.content()), Some("Pick a planet"));
+        assert!(body.directives().is_empty());
+        assert_eq!(body.should_end_session(), Some(false));
+        assert_eq!(res.attributes().unwrap()["step"], "pick");
+        res.remove_attribute("step");
+        assert!(res.attributes().unwrap().get("step").is_none());
+    }
+
+    #[test]
+    fn test_can_fulfill() {
+        let res = Response::new(true).can_fulfill(CanFulfillIntent::new(CanFulfill::Yes).slot(
+            "city",
+            CanFulfill::Yes,
+            CanFulfill::Yes,
+        ));
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["response"]["canFulfillIntent"]["canFulfill"], "YES");
+        assert_eq!(
+            v["response"]["canFulfillIntent"]["slots"]["city"]["canFulfill"],
+            "YES"
+        );
+        let c = res.body().can_fulfill_intent().unwrap();
+        assert_eq!(c.can_fulfill(), CanFulfill::Yes);
+    }
+
+    #[test]
+    fn test_api_response() {
+        let res = Response::new(false)
+            .api_response(serde_json::json!({"weather": "sunny", "highTemp": 25}));
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["response"]["apiResponse"]["weather"], "sunny");
+        assert_eq!(v["response"]["apiResponse"]["highTemp"], 25);
+        assert_eq!(res.body().api_response().unwrap()["weather"], "sunny");
+        let v = serde_json::to_value(Response::end()).unwrap();
+        assert!(v["response"].get("apiResponse").is_none());
+    }
+
+    #[test]
+    fn test_trigger_experiment() {
+        let res = Response::simple("hello", "welcome back")
+            .trigger_experiment("exp-welcome")
+            .trigger_experiment("exp-card");
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(
+            v["response"]["experimentation"]["triggeredExperiments"],
+            serde_json::json!(["exp-welcome", "exp-card"])
+        );
+        assert_eq!(res.body().triggered_experiments().len(), 2);
+        let v = serde_json::to_value(Response::end()).unwrap();
+        assert!(v["response"].get("experimentation").is_none());
+    }
+
+    #[test]
+    fn test_directive_types() {
+        let apla = Document::apla(serde_json::json!({"item": {"type": "Speech", "content": "hi"}}));
+        let aplt = Document::aplt(serde_json::json!({"item": {"type": "Text", "text": "HI"}}));
+        let cases = vec![
+            (Directive::hint("ask for a planet"), "Hint", Some("Display")),
+            (
+                Directive::render_template(Template::new(TemplateType::BodyTemplate1)),
+                "Display.RenderTemplate",
+                Some("Display"),
+            ),
+            (
+                Directive::RenderDocument(RenderDocument::link("doc://alexa/apl/documents/x")),
+                "Alexa.Presentation.APL.RenderDocument",
+                Some("Alexa.Presentation.APL"),
+            ),
+            (
+                Directive::ExecuteCommands(ExecuteCommands::new(
+                    "planets",
+                    vec![Command::set_page("pager", 1)],
+                )),
+                "Alexa.Presentation.APL.ExecuteCommands",
+                Some("Alexa.Presentation.APL"),
+            ),
+            (
+                Directive::SendIndexListData(SendIndexListData::new("planets", 0, vec![])),
+                "Alexa.Presentation.APL.SendIndexListData",
+                Some("Alexa.Presentation.APL"),
+            ),
+            (
+                Directive::UpdateIndexListData(UpdateIndexListData::new("doc", "planets", 1)),
+                "Alexa.Presentation.APL.UpdateIndexListData",
+                Some("Alexa.Presentation.APL"),
+            ),
+            (
+                Directive::SendTokenListData(SendTokenListData::new("planets", "p1", vec![])),
+                "Alexa.Presentation.APL.SendTokenListData",
+                Some("Alexa.Presentation.APL"),
+            ),
+            (
+                Directive::AplaRenderDocument(RenderDocument::from_document(apla)),
+                "Alexa.Presentation.APLA.RenderDocument",
+                None,
+            ),
+            (
+                Directive::ApltRenderDocument(ApltRenderDocument::from_document(
+                    TargetProfile::FourCharacterClock,
+                    aplt,
+                )),
+                "Alexa.Presentation.APLT.RenderDocument",
+                Some("Alexa.Presentation.APLT"),
+            ),
+            (
+                Directive::HtmlStart(Start::new("https://example.com/game", 300)),
+                "Alexa.Presentation.HTML.Start",
+                Some("Alexa.Presentation.HTML"),
+            ),
+            (
+                Directive::HandleMessage(HandleMessage::new(serde_json::json!({"event": "start"}))),
+                "Alexa.Presentation.HTML.HandleMessage",
+                Some("Alexa.Presentation.HTML"),
+            ),
+            (
+                Directive::SendRequest(SendRequest::buy("amzn1.adg.product.1", "buy-1")),
+                "Connections.SendRequest",
+                None,
+            ),
+            (
+                Directive::VideoAppLaunch(Launch::new("https://example.com/video.mp4")),
+                "VideoApp.Launch",
+                Some("VideoApp"),
+            ),
+        ];
+        for (d, directive_type, interface) in cases {
+            assert_eq!(d.interface(), interface, "{}", directive_type);
+            let v = serde_json::to_value(Response::new(true).directive(d)).unwrap();
+            assert_eq!(v["response"]["directives"][0]["type"], directive_type);
+        }
+    }
+
+    #[test]
+    fn test_elicit_slot() {
+        let v = serde_json::to_value(Response::elicit_slot("planet", "which planet?")).unwrap();
+        assert_eq!(v["response"]["outputSpeech"]["text"], "which planet?");
+        assert_eq!(v["response"]["shouldEndSession"], false);
+        let d = &v["response"]["directives"][0];
+        assert_eq!(d["type"], "Dialog.ElicitSlot");
+        assert_eq!(d["slotToElicit"], "planet");
+        assert!(d.get("updatedIntent").is_none());
+    }
+
+    #[test]
+    fn test_reprompt() {
+        let res = Response::new(false)
+            .speech(Speech::plain("which planet?"))
+            .reprompt(Speech::ssml("<speak>which planet?</speak>"));
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["response"]["reprompt"]["outputSpeech"]["type"], "SSML");
+        assert_eq!(
+            v["response"]["reprompt"]["outputSpeech"]["ssml"],
+            "<speak>which planet?</speak>"
+        );
+        assert_eq!(v["response"]["shouldEndSession"], false);
+    }
+
+    #[test]
+    fn test_default_reprompt() {
+        let res = Response::new(false)
+            .speech(Speech::plain("hola"))
+            .default_reprompt(&Locale::MexicanSpanish);
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(
+            v["response"]["reprompt"]["outputSpeech"]["text"],
+            "¿Hay algo más en lo que pueda ayudarte?"
+        );
+
+        let res = Response::new(false)
+            .reprompt(Speech::plain("which planet?"))
+            .default_reprompt(&Locale::AmericanEnglish);
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(
+            v["response"]["reprompt"]["outputSpeech"]["text"],
+            "which planet?"
+        );
+
+        let res = Response::new(false).default_reprompt(&Locale::Dutch);
+        assert_eq!(
+            res.body.reprompt().unwrap().text(),
+            Some("Kan ik je nog ergens anders mee helpen?")
+        );
+
+        let res = Response::end().default_reprompt(&Locale::AmericanEnglish);
+        assert!(res.body.reprompt.is_none());
+    }
+
+    #[test]
+    fn test_unknown_directive() {
+        let raw = serde_json::json!({
+            "type": "AudioPlayer.ClearQueue",
+            "clearBehavior": "CLEAR_ALL"
+        });
+        let d: Directive = serde_json::from_value(raw.clone()).unwrap();
+        match d {
+            Directive::Other(ref v) => assert_eq!(*v, raw),
+            ref d => panic!("expected unknown directive, got {:?}", d),
+        }
+        assert_eq!(serde_json::to_value(&d).unwrap(), raw);
+        let known: Directive = serde_json::from_value(
+            serde_json::json!({"type": "Dialog.ElicitSlot", "slotToElicit": "name"}),
+        )
+        .unwrap();
+        assert!(matches!(known, Directive::ElicitSlot(_)));
+    }
+
+    #[test]
+    fn test_add_supported_directive() {
+        let req: Request = serde_json::from_value(serde_json::json!({
+            "version": "1.0",
+            "context": {
+                "System": {
+                    "device": {
+                        "deviceId": "amzn1.ask.device.1",
+                        "supportedInterfaces": {}
+                    }
+                }
+            },
+            "request": {
+                "type": "LaunchRequest",
+                "requestId": "amzn1.echo-api.request.1",
+                "timestamp": "2019-03-01T10:00:00Z"
+            }
+        }))
+        .unwrap();
+        let mut res = Response::new(false);
+        assert!(!res.add_supported_directive(
+            &req,
+            Directive::StartInputHandler(StartInputHandler::new(5000))
+        ));
+        assert!(res.add_supported_directive(&req, Directive::complete_task("200", "done")));
+        assert_eq!(res.body.directives.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_response_macro() {
+        let name = "bob";
+        let res = alexa_response! {
+            speech: ssml!("<speak>hello {}</speak>", name),
+            end_session: false,
+            reprompt: Speech::plain("still there?"),
+            attributes: { "name" => name },
+            directives: [Directive::complete_task("200", "done")],
+        };
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(
+            v["response"]["outputSpeech"]["ssml"],
+            "<speak>hello bob</speak>"
+        );
+        assert_eq!(v["response"]["shouldEndSession"], false);
+        assert_eq!(
+            v["response"]["reprompt"]["outputSpeech"]["text"],
+            "still there?"
+        );
+        assert_eq!(v["sessionAttributes"]["name"], "bob");
+        assert_eq!(v["response"]["directives"][0]["type"], "Tasks.CompleteTask");
+
+        let res = alexa_response! {};
+        assert_eq!(res.body.should_end_session, Some(true));
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut old = Response::new(false).speech(Speech::plain("hello"));
+        old.add_attribute("count", "1");
+        let mut new = Response::new(false).speech(Speech::plain("hi there"));
+        new.add_directive(Directive::complete_task("200", "done"));
+
+        let diffs = old.diff(&new);
+        assert_eq!(diffs.len(), 3);
+        assert!(diffs.contains(&Difference::Changed(
+            String::from("response.outputSpeech.text"),
+            Value::from("hello"),
+            Value::from("hi there")
+        )));
+        assert!(diffs.contains(&Difference::Removed(
+            String::from("sessionAttributes"),
+            serde_json::json!({"count": "1"})
+        )));
+        assert!(diffs.iter().any(|d| d.path() == "response.directives"));
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_set_attr() {
+        #[derive(Serialize)]
+        struct Game {
+            level: u32,
+            players: Vec<&'static str>,
+        }
+
+        let mut res = Response::new(false);
+        res.set_attr("count", &3).unwrap();
+        res.set_attr(
+            "game",
+            &Game {
+                level: 2,
+                players: vec!["ann", "bob"],
+            },
+        )
+        .unwrap();
+        res.add_attribute("name", "bob");
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["sessionAttributes"]["count"], 3);
+        assert_eq!(v["sessionAttributes"]["game"]["players"][1], "bob");
+        assert_eq!(v["sessionAttributes"]["name"], "bob");
+    }
+
+    #[test]
+    fn test_round_trip_unknown_fields() {
+        let original = serde_json::json!({
+            "version": "1.0",
+            "userAgent": "ask-node/2.0.0",
+            "response": {
+                "outputSpeech": {"type": "PlainText", "text": "hello"},
+                "shouldEndSession": true,
+                "futureFeature": {"enabled": true}
+            }
+        });
+        let res: Response = serde_json::from_value(original.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&res).unwrap(), original);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = Response::simple("hello", "hello world").to_json().unwrap();
+        let v: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["response"]["outputSpeech"]["text"], "hello world");
+    }
 
     #[test]
     fn test_version() {
         let r = Response::simple("hello, world", "hello, dude");
-        assert_eq!(r.version, "1.0");
+        assert_eq!(r.version, Version::V1_0);
+        assert_eq!(r.spec_version(), Version::V1_0);
+    }
+
+    #[test]
+    fn test_other_version() {
+        let r = Response::end().version(Version::from("2.0"));
+        assert_eq!(r.spec_version(), Version::Other(String::from("2.0")));
+        let v = serde_json::to_value(&r).unwrap();
+        assert_eq!(v["version"], "2.0");
+        let r: Response = serde_json::from_value(v).unwrap();
+        assert_eq!(r.spec_version(), Version::Other(String::from("2.0")));
     }
 
     #[test]
@@ -405,9 +1432,22 @@ mod tests {
         assert_eq!(r.body.card.unwrap().content.unwrap(), t);
     }
 
+    #[test]
+    fn test_complete_task() {
+        let r = Response::complete_task("200", "played the sound");
+        let v = serde_json::to_value(&r).unwrap();
+        assert_eq!(v["response"]["shouldEndSession"], true);
+        assert_eq!(v["response"]["directives"][0]["type"], "Tasks.CompleteTask");
+        assert_eq!(v["response"]["directives"][0]["status"]["code"], "200");
+        assert_eq!(
+            v["response"]["directives"][0]["status"]["message"],
+            "played the sound"
+        );
+    }
+
     #[test]
     fn test_should_end() {
         let r = Response::simple("foo", "bar");
-        assert_eq!(r.body.should_end_session, true);
+        assert_eq!(r.body.should_end_session, Some(true));
     }
 }
@@ -0,0 +1,487 @@
+//! Typed values of the built-in slot types
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Error returned when a slot value can't be converted to a typed value
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotError {
+    /// the slot has not been filled
+    Missing,
+    /// Alexa heard something but could not recognize it ("?")
+    Unrecognized,
+    /// the value does not have the format of the slot type
+    Invalid(String),
+}
+
+impl fmt::Display for SlotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SlotError::Missing => write!(f, "slot has no value"),
+            SlotError::Unrecognized => write!(f, "slot value was not recognized"),
+            SlotError::Invalid(v) => write!(f, "invalid slot value: {}", v),
+        }
+    }
+}
+
+impl Error for SlotError {}
+
+/// the value of a filled slot, or the reason it can't be used
+pub(crate) fn filled(value: Option<&str>) -> Result<&str, SlotError> {
+    match value {
+        None => Err(SlotError::Missing),
+        Some("?") => Err(SlotError::Unrecognized),
+        Some(v) => Ok(v),
+    }
+}
+
+/// Day of the (proleptic Gregorian) calendar
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl CalendarDate {
+    fn from_days(z: i64) -> CalendarDate {
+        let (y, m, d) = civil_from_days(z);
+        CalendarDate {
+            year: y as i32,
+            month: m as u32,
+            day: d as u32,
+        }
+    }
+
+    fn last_of_month(year: i32, month: u32) -> CalendarDate {
+        let (y, m) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+        CalendarDate::from_days(days_from_civil(y as i64, m as i64, 1) - 1)
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + if m <= 2 { 1 } else { 0 }, m, d)
+}
+
+/// Monday of the given ISO 8601 week, in days since 1970-01-01
+fn iso_week_monday(year: i32, week: u32) -> i64 {
+    let jan4 = days_from_civil(year as i64, 1, 4);
+    let weekday = (jan4 + 3).rem_euclid(7);
+    jan4 - weekday + 7 * (week as i64 - 1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Season {
+    Winter,
+    Spring,
+    Summer,
+    Fall,
+}
+
+/// Value of an AMAZON.DATE slot, see the
+/// [slot type reference](https://developer.amazon.com/docs/custom-skills/slot-type-reference.html#date)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlotDate {
+    /// "2015-11-24"
+    Day(CalendarDate),
+    /// "2015-W48", an ISO 8601 week
+    Week { year: i32, week: u32 },
+    /// "2015-W48-WE"
+    Weekend { year: i32, week: u32 },
+    /// "2015-11"
+    Month { year: i32, month: u32 },
+    /// "2017-WI"
+    Season { year: i32, season: Season },
+    /// "2015"
+    Year(i32),
+    /// "201X", holding the first year of the decade
+    Decade(i32),
+    /// "PRESENT_REF", i.e. "now"
+    Present,
+}
+
+impl SlotDate {
+    /// the first and last day (inclusive) covered by the value, `None` for
+    /// `Present`; seasons are the meteorological seasons of the northern
+    /// hemisphere, winter running from December into the next year
+    pub fn range(&self) -> Option<(CalendarDate, CalendarDate)> {
+        let days = |y: i32, m: u32, d: u32| CalendarDate {
+            year: y,
+            month: m,
+            day: d,
+        };
+        let range = match *self {
+            SlotDate::Day(d) => (d, d),
+            SlotDate::Week { year, week } => {
+                let monday = iso_week_monday(year, week);
+                (
+                    CalendarDate::from_days(monday),
+                    CalendarDate::from_days(monday + 6),
+                )
+            }
+            SlotDate::Weekend { year, week } => {
+                let monday = iso_week_monday(year, week);
+                (
+                    CalendarDate::from_days(monday + 5),
+                    CalendarDate::from_days(monday + 6),
+                )
+            }
+            SlotDate::Month { year, month } => (
+                days(year, month, 1),
+                CalendarDate::last_of_month(year, month),
+            ),
+            SlotDate::Season { year, season } => match season {
+                Season::Spring => (days(year, 3, 1), days(year, 5, 31)),
+                Season::Summer => (days(year, 6, 1), days(year, 8, 31)),
+                Season::Fall => (days(year, 9, 1), days(year, 11, 30)),
+                Season::Winter => (days(year, 12, 1), CalendarDate::last_of_month(year + 1, 2)),
+            },
+            SlotDate::Year(year) => (days(year, 1, 1), days(year, 12, 31)),
+            SlotDate::Decade(year) => (days(year, 1, 1), days(year + 9, 12, 31)),
+            SlotDate::Present => return None,
+        };
+        Some(range)
+    }
+}
+
+fn number<T: FromStr>(s: &str, digits: usize) -> Option<T> {
+    if s.len() != digits || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl FromStr for SlotDate {
+    type Err = SlotError;
+
+    fn from_str(s: &str) -> Result<SlotDate, SlotError> {
+        let invalid = || SlotError::Invalid(String::from(s));
+        if s == "PRESENT_REF" {
+            return Ok(SlotDate::Present);
+        }
+        let parts: Vec<&str> = s.split('-').collect();
+        if parts[0].len() == 4 && parts[0].ends_with('X') && parts.len() == 1 {
+            let decade: i32 = number(&parts[0][..3], 3).ok_or_else(invalid)?;
+            return Ok(SlotDate::Decade(decade * 10));
+        }
+        let year: i32 = number(parts[0], 4).ok_or_else(invalid)?;
+        let date = match parts[1..] {
+            [] => SlotDate::Year(year),
+            ["WI"] | ["SP"] | ["SU"] | ["FA"] => SlotDate::Season {
+                year,
+                season: match parts[1] {
+                    "WI" => Season::Winter,
+                    "SP" => Season::Spring,
+                    "SU" => Season::Summer,
+                    _ => Season::Fall,
+                },
+            },
+            [w] | [w, "WE"] if w.starts_with('W') => {
+                let week = number(&w[1..], 2)
+                    .filter(|w| (1..=53).contains(w))
+                    .ok_or_else(invalid)?;
+                if parts.len() == 3 {
+                    SlotDate::Weekend { year, week }
+                } else {
+                    SlotDate::Week { year, week }
+                }
+            }
+            [m] => SlotDate::Month {
+                year,
+                month: number(m, 2)
+                    .filter(|m| (1..=12).contains(m))
+                    .ok_or_else(invalid)?,
+            },
+            [m, d] => {
+                let month = number(m, 2)
+                    .filter(|m| (1..=12).contains(m))
+                    .ok_or_else(invalid)?;
+                let day = number(d, 2)
+                    .filter(|d| *d >= 1 && *d <= CalendarDate::last_of_month(year, month).day)
+                    .ok_or_else(invalid)?;
+                SlotDate::Day(CalendarDate { year, month, day })
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(date)
+    }
+}
+
+/// Value of an AMAZON.DURATION slot, an ISO 8601 duration such as "PT10M"
+/// or "P3D"; years and months are kept apart as their length varies
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SlotDuration {
+    pub years: u32,
+    pub months: u32,
+    /// the weeks, days, hours, minutes and seconds of the duration
+    pub time: Duration,
+}
+
+impl SlotDuration {
+    /// the duration as a `std::time::Duration`, if it has no year or month
+    /// components
+    pub fn to_std(&self) -> Option<Duration> {
+        if self.years == 0 && self.months == 0 {
+            Some(self.time)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for SlotDuration {
+    type Err = SlotError;
+
+    fn from_str(s: &str) -> Result<SlotDuration, SlotError> {
+        let invalid = || SlotError::Invalid(String::from(s));
+        let rest = s.strip_prefix('P').ok_or_else(invalid)?;
+        let (date, time) = match rest.find('T') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        let mut d = SlotDuration::default();
+        let mut seconds = 0f64;
+        let mut any = false;
+        for (part, in_time) in [(date, false), (time.unwrap_or(""), true)].iter() {
+            // designators must appear at most once and in this order
+            let designators: &[char] = if *in_time {
+                &['H', 'M', 'S']
+            } else {
+                &['Y', 'M', 'W', 'D']
+            };
+            let mut next = 0;
+            let mut num = String::new();
+            for c in part.chars() {
+                if c.is_ascii_digit() || (*in_time && c == '.') {
+                    num.push(c);
+                    continue;
+                }
+                let i = designators[next..]
+                    .iter()
+                    .position(|d| *d == c)
+                    .ok_or_else(invalid)?;
+                next += i + 1;
+                any = true;
+                match (c, *in_time) {
+                    ('Y', false) => d.years = num.parse().map_err(|_| invalid())?,
+                    ('M', false) => d.months = num.parse().map_err(|_| invalid())?,
+                    _ => {
+                        let n: f64 = num.parse().map_err(|_| invalid())?;
+                        seconds += n * match (c, *in_time) {
+                            ('W', false) => 604_800.0,
+                            ('D', false) => 86_400.0,
+                            ('H', true) => 3_600.0,
+                            ('M', true) => 60.0,
+                            _ => 1.0,
+                        };
+                    }
+                }
+                num.clear();
+            }
+            if !num.is_empty() {
+                return Err(invalid());
+            }
+        }
+        if !any || time == Some("") {
+            return Err(invalid());
+        }
+        d.time = Duration::try_from_secs_f64(seconds).map_err(|_| invalid())?;
+        Ok(d)
+    }
+}
+
+/// Value of an AMAZON.TIME slot: a time of day, or a period of the day when
+/// the user was vague ("this evening")
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlotTime {
+    /// "14:25"
+    At { hour: u32, minute: u32 },
+    /// "MO"
+    Morning,
+    /// "AF"
+    Afternoon,
+    /// "EV"
+    Evening,
+    /// "NI"
+    Night,
+}
+
+impl FromStr for SlotTime {
+    type Err = SlotError;
+
+    fn from_str(s: &str) -> Result<SlotTime, SlotError> {
+        let invalid = || SlotError::Invalid(String::from(s));
+        let time = match s {
+            "MO" => SlotTime::Morning,
+            "AF" => SlotTime::Afternoon,
+            "EV" => SlotTime::Evening,
+            "NI" => SlotTime::Night,
+            _ => {
+                let mut parts = s.split(':');
+                let (h, m) = match (parts.next(), parts.next(), parts.next()) {
+                    (Some(h), Some(m), None) => (h, m),
+                    _ => return Err(invalid()),
+                };
+                SlotTime::At {
+                    hour: number(h, 2).filter(|h| *h < 24).ok_or_else(invalid)?,
+                    minute: number(m, 2).filter(|m| *m < 60).ok_or_else(invalid)?,
+                }
+            }
+        };
+        Ok(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+        CalendarDate { year, month, day }
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!("2015-11-24".parse(), Ok(SlotDate::Day(date(2015, 11, 24))));
+        assert_eq!(
+            "2015-W48".parse(),
+            Ok(SlotDate::Week {
+                year: 2015,
+                week: 48
+            })
+        );
+        assert_eq!(
+            "2015-W48-WE".parse(),
+            Ok(SlotDate::Weekend {
+                year: 2015,
+                week: 48
+            })
+        );
+        assert_eq!(
+            "2015-11".parse(),
+            Ok(SlotDate::Month {
+                year: 2015,
+                month: 11
+            })
+        );
+        assert_eq!(
+            "2017-WI".parse(),
+            Ok(SlotDate::Season {
+                year: 2017,
+                season: Season::Winter
+            })
+        );
+        assert_eq!("2015".parse(), Ok(SlotDate::Year(2015)));
+        assert_eq!("201X".parse(), Ok(SlotDate::Decade(2010)));
+        assert_eq!("PRESENT_REF".parse(), Ok(SlotDate::Present));
+        for invalid in &["2015-02-30", "2015-13", "2015-W54", "next tuesday", ""] {
+            assert_eq!(
+                invalid.parse::<SlotDate>(),
+                Err(SlotError::Invalid(String::from(*invalid)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_range() {
+        let range = |s: &str| s.parse::<SlotDate>().unwrap().range().unwrap();
+        assert_eq!(range("2015-W48"), (date(2015, 11, 23), date(2015, 11, 29)));
+        assert_eq!(
+            range("2015-W48-WE"),
+            (date(2015, 11, 28), date(2015, 11, 29))
+        );
+        assert_eq!(range("2021-W01"), (date(2021, 1, 4), date(2021, 1, 10)));
+        assert_eq!(range("2016-02"), (date(2016, 2, 1), date(2016, 2, 29)));
+        assert_eq!(range("2015-WI"), (date(2015, 12, 1), date(2016, 2, 29)));
+        assert_eq!(range("201X"), (date(2010, 1, 1), date(2019, 12, 31)));
+        assert!(SlotDate::Present.range().is_none());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let std = |s: &str| s.parse::<SlotDuration>().unwrap().to_std();
+        assert_eq!(std("PT10M"), Some(Duration::from_secs(600)));
+        assert_eq!(std("P3D"), Some(Duration::from_secs(3 * 86_400)));
+        assert_eq!(std("P1W"), Some(Duration::from_secs(604_800)));
+        assert_eq!(std("PT1H30M15S"), Some(Duration::from_secs(5_415)));
+        assert_eq!(std("PT0.5S"), Some(Duration::from_millis(500)));
+        assert_eq!(
+            "P1Y2MT3M".parse(),
+            Ok(SlotDuration {
+                years: 1,
+                months: 2,
+                time: Duration::from_secs(180),
+            })
+        );
+        assert_eq!(std("P1Y"), None);
+        for invalid in &[
+            "10 minutes",
+            "P",
+            "PT",
+            "P3",
+            "PT5D",
+            "P2H",
+            "P99999999999999999999999D",
+            "P4294967295Y1Y",
+            "P99999999999Y",
+            "PT1M1M",
+            "PT1S1M",
+            "P1D1W",
+            "P1.5Y",
+        ] {
+            assert_eq!(
+                invalid.parse::<SlotDuration>(),
+                Err(SlotError::Invalid(String::from(*invalid)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            "14:25".parse(),
+            Ok(SlotTime::At {
+                hour: 14,
+                minute: 25
+            })
+        );
+        assert_eq!("EV".parse(), Ok(SlotTime::Evening));
+        assert_eq!("NI".parse(), Ok(SlotTime::Night));
+        for invalid in &["24:00", "7:30", "12:30:00", "noon"] {
+            assert_eq!(
+                invalid.parse::<SlotTime>(),
+                Err(SlotError::Invalid(String::from(*invalid)))
+            );
+        }
+    }
+}
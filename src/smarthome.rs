@@ -0,0 +1,756 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+const PAYLOAD_VERSION: &str = "3";
+
+/// Smart Home directive corresponding to the [Alexa Smart Home spec](https://developer.amazon.com/docs/device-apis/message-guide.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Request {
+    pub directive: Directive,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Directive {
+    pub header: Header,
+    pub endpoint: Option<Endpoint>,
+    pub payload: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Header {
+    pub namespace: String,
+    pub name: String,
+    #[serde(rename = "payloadVersion")]
+    pub payload_version: String,
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "correlationToken")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Endpoint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cookie: Option<HashMap<String, String>>,
+}
+
+impl Endpoint {
+    /// Constructs an endpoint reference with only the endpoint ID
+    pub fn new(endpoint_id: &str) -> Endpoint {
+        Endpoint {
+            scope: None,
+            endpoint_id: String::from(endpoint_id),
+            cookie: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Scope {
+    #[serde(rename = "type")]
+    pub scope_type: String,
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Temperature {
+    pub value: f64,
+    pub scale: String,
+}
+
+impl Temperature {
+    pub fn celsius(value: f64) -> Temperature {
+        Temperature {
+            value,
+            scale: TemperatureScale::Celsius.to_string(),
+        }
+    }
+
+    pub fn fahrenheit(value: f64) -> Temperature {
+        Temperature {
+            value,
+            scale: TemperatureScale::Fahrenheit.to_string(),
+        }
+    }
+}
+
+/// Temperature scales used by the ThermostatController interface
+pub enum TemperatureScale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl fmt::Display for TemperatureScale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            TemperatureScale::Celsius => "CELSIUS",
+            TemperatureScale::Fahrenheit => "FAHRENHEIT",
+            TemperatureScale::Kelvin => "KELVIN",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Enumeration of Smart Home directives
+/// Not comprehensive, ones not defined are put into the Other value
+/// with the directive namespace and name
+#[derive(Debug, PartialEq)]
+pub enum DirectiveType {
+    Discover,
+    ReportState,
+    TurnOn,
+    TurnOff,
+    SetBrightness(u8),
+    AdjustBrightness(i8),
+    SetTargetTemperature(Temperature),
+    AdjustTargetTemperature(Temperature),
+    SetThermostatMode(String),
+    Other(String, String),
+}
+
+impl Request {
+    /// Extracts the typed directive (with its payload) from the request
+    pub fn directive_type(&self) -> DirectiveType {
+        let h = &self.directive.header;
+        let p = &self.directive.payload;
+        match (h.namespace.as_str(), h.name.as_str()) {
+            ("Alexa.Discovery", "Discover") => DirectiveType::Discover,
+            ("Alexa", "ReportState") => DirectiveType::ReportState,
+            ("Alexa.PowerController", "TurnOn") => DirectiveType::TurnOn,
+            ("Alexa.PowerController", "TurnOff") => DirectiveType::TurnOff,
+            ("Alexa.BrightnessController", "SetBrightness") => match p["brightness"].as_u64() {
+                Some(b) if b <= 100 => DirectiveType::SetBrightness(b as u8),
+                _ => DirectiveType::Other(h.namespace.clone(), h.name.clone()),
+            },
+            ("Alexa.BrightnessController", "AdjustBrightness") => {
+                match p["brightnessDelta"].as_i64() {
+                    Some(d) if (-100..=100).contains(&d) => {
+                        DirectiveType::AdjustBrightness(d as i8)
+                    }
+                    _ => DirectiveType::Other(h.namespace.clone(), h.name.clone()),
+                }
+            }
+            ("Alexa.ThermostatController", "SetTargetTemperature") => {
+                match serde_json::from_value(p["targetSetpoint"].clone()) {
+                    Ok(t) => DirectiveType::SetTargetTemperature(t),
+                    Err(_) => DirectiveType::Other(h.namespace.clone(), h.name.clone()),
+                }
+            }
+            ("Alexa.ThermostatController", "AdjustTargetTemperature") => {
+                match serde_json::from_value(p["targetSetpointDelta"].clone()) {
+                    Ok(t) => DirectiveType::AdjustTargetTemperature(t),
+                    Err(_) => DirectiveType::Other(h.namespace.clone(), h.name.clone()),
+                }
+            }
+            ("Alexa.ThermostatController", "SetThermostatMode") => {
+                match p["thermostatMode"]["value"].as_str() {
+                    Some(m) => DirectiveType::SetThermostatMode(String::from(m)),
+                    None => DirectiveType::Other(h.namespace.clone(), h.name.clone()),
+                }
+            }
+            _ => DirectiveType::Other(h.namespace.clone(), h.name.clone()),
+        }
+    }
+
+    /// retrieves the endpoint ID the directive is targeting, if any
+    pub fn endpoint_id(&self) -> Option<&str> {
+        Some(&self.directive.endpoint.as_ref()?.endpoint_id)
+    }
+
+    /// retrieves the bearer token of the account linked user, from either
+    /// the endpoint scope or (for discovery) the payload scope
+    pub fn bearer_token(&self) -> Option<&str> {
+        if let Some(scope) = self
+            .directive
+            .endpoint
+            .as_ref()
+            .and_then(|e| e.scope.as_ref())
+        {
+            return Some(&scope.token);
+        }
+        self.directive.payload["scope"]["token"].as_str()
+    }
+
+    /// retrieves the correlation token that must be echoed in the response
+    pub fn correlation_token(&self) -> Option<&str> {
+        self.directive.header.correlation_token.as_deref()
+    }
+}
+
+/// Error types for a Smart Home error response
+pub enum ErrorType {
+    EndpointUnreachable,
+    NoSuchEndpoint,
+    InvalidValue,
+    ValueOutOfRange,
+    TemperatureValueOutOfRange,
+    InvalidDirective,
+    InvalidAuthorizationCredential,
+    ExpiredAuthorizationCredential,
+    NotSupportedInCurrentMode,
+    EndpointBusy,
+    EndpointLowPower,
+    InternalError,
+}
+
+impl fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            ErrorType::EndpointUnreachable => "ENDPOINT_UNREACHABLE",
+            ErrorType::NoSuchEndpoint => "NO_SUCH_ENDPOINT",
+            ErrorType::InvalidValue => "INVALID_VALUE",
+            ErrorType::ValueOutOfRange => "VALUE_OUT_OF_RANGE",
+            ErrorType::TemperatureValueOutOfRange => "TEMPERATURE_VALUE_OUT_OF_RANGE",
+            ErrorType::InvalidDirective => "INVALID_DIRECTIVE",
+            ErrorType::InvalidAuthorizationCredential => "INVALID_AUTHORIZATION_CREDENTIAL",
+            ErrorType::ExpiredAuthorizationCredential => "EXPIRED_AUTHORIZATION_CREDENTIAL",
+            ErrorType::NotSupportedInCurrentMode => "NOT_SUPPORTED_IN_CURRENT_MODE",
+            ErrorType::EndpointBusy => "ENDPOINT_BUSY",
+            ErrorType::EndpointLowPower => "ENDPOINT_LOW_POWER",
+            ErrorType::InternalError => "INTERNAL_ERROR",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Cause of a change report
+pub enum ChangeCause {
+    AppInteraction,
+    PhysicalInteraction,
+    PeriodicPoll,
+    RuleTrigger,
+    VoiceInteraction,
+}
+
+impl fmt::Display for ChangeCause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            ChangeCause::AppInteraction => "APP_INTERACTION",
+            ChangeCause::PhysicalInteraction => "PHYSICAL_INTERACTION",
+            ChangeCause::PeriodicPoll => "PERIODIC_POLL",
+            ChangeCause::RuleTrigger => "RULE_TRIGGER",
+            ChangeCause::VoiceInteraction => "VOICE_INTERACTION",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Smart Home event (response, state report, change report, discovery response,
+/// or error response) sent back to Alexa
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Response {
+    event: Event,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<Context>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Event {
+    header: Header,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint: Option<Endpoint>,
+    payload: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Context {
+    properties: Vec<Property>,
+}
+
+/// A reported property of an endpoint
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Property {
+    namespace: String,
+    name: String,
+    value: Value,
+    #[serde(rename = "timeOfSample")]
+    time_of_sample: String,
+    #[serde(rename = "uncertaintyInMilliseconds")]
+    uncertainty_in_milliseconds: u64,
+}
+
+impl Property {
+    /// Constructs a property; `time_of_sample` is an ISO 8601 timestamp
+    pub fn new(namespace: &str, name: &str, value: Value, time_of_sample: &str) -> Property {
+        Property {
+            namespace: String::from(namespace),
+            name: String::from(name),
+            value,
+            time_of_sample: String::from(time_of_sample),
+            uncertainty_in_milliseconds: 0,
+        }
+    }
+
+    /// Constructs a PowerController powerState property
+    pub fn power_state(on: bool, time_of_sample: &str) -> Property {
+        let v = if on { "ON" } else { "OFF" };
+        Property::new(
+            "Alexa.PowerController",
+            "powerState",
+            Value::from(v),
+            time_of_sample,
+        )
+    }
+
+    /// Constructs a BrightnessController brightness property
+    pub fn brightness(brightness: u8, time_of_sample: &str) -> Property {
+        Property::new(
+            "Alexa.BrightnessController",
+            "brightness",
+            Value::from(brightness),
+            time_of_sample,
+        )
+    }
+
+    /// Constructs a ThermostatController targetSetpoint property
+    pub fn target_setpoint(temperature: Temperature, time_of_sample: &str) -> Property {
+        Property::new(
+            "Alexa.ThermostatController",
+            "targetSetpoint",
+            serde_json::to_value(temperature).unwrap_or(Value::Null),
+            time_of_sample,
+        )
+    }
+
+    /// Constructs a ThermostatController thermostatMode property
+    pub fn thermostat_mode(mode: &str, time_of_sample: &str) -> Property {
+        Property::new(
+            "Alexa.ThermostatController",
+            "thermostatMode",
+            Value::from(mode),
+            time_of_sample,
+        )
+    }
+
+    /// Constructs an EndpointHealth connectivity property
+    pub fn connectivity(reachable: bool, time_of_sample: &str) -> Property {
+        let v = if reachable { "OK" } else { "UNREACHABLE" };
+        let mut value = serde_json::Map::new();
+        value.insert(String::from("value"), Value::from(v));
+        Property::new(
+            "Alexa.EndpointHealth",
+            "connectivity",
+            Value::Object(value),
+            time_of_sample,
+        )
+    }
+
+    /// sets the uncertainty of the sampled value
+    pub fn uncertainty(mut self, millis: u64) -> Self {
+        self.uncertainty_in_milliseconds = millis;
+        self
+    }
+}
+
+/// Endpoint description returned in a discovery response
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DiscoveryEndpoint {
+    #[serde(rename = "endpointId")]
+    endpoint_id: String,
+    #[serde(rename = "manufacturerName")]
+    manufacturer_name: String,
+    #[serde(rename = "friendlyName")]
+    friendly_name: String,
+    description: String,
+    #[serde(rename = "displayCategories")]
+    display_categories: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cookie: Option<HashMap<String, String>>,
+    capabilities: Vec<Capability>,
+}
+
+impl DiscoveryEndpoint {
+    /// Constructs a discoverable endpoint; the Alexa interface capability is
+    /// always included
+    pub fn new(
+        endpoint_id: &str,
+        manufacturer_name: &str,
+        friendly_name: &str,
+        description: &str,
+    ) -> DiscoveryEndpoint {
+        DiscoveryEndpoint {
+            endpoint_id: String::from(endpoint_id),
+            manufacturer_name: String::from(manufacturer_name),
+            friendly_name: String::from(friendly_name),
+            description: String::from(description),
+            display_categories: Vec::new(),
+            cookie: None,
+            capabilities: vec![Capability::alexa()],
+        }
+    }
+
+    /// adds a display category (e.g. LIGHT, THERMOSTAT, SMARTPLUG)
+    pub fn display_category(mut self, category: &str) -> Self {
+        self.display_categories.push(String::from(category));
+        self
+    }
+
+    /// adds a capability
+    pub fn capability(mut self, capability: Capability) -> Self {
+        self.capabilities.push(capability);
+        self
+    }
+
+    /// adds a cookie key/value pair, sent back on every directive for the endpoint
+    pub fn cookie(mut self, key: &str, val: &str) -> Self {
+        self.cookie
+            .get_or_insert_with(HashMap::new)
+            .insert(String::from(key), String::from(val));
+        self
+    }
+}
+
+/// Capability (interface) supported by a discovered endpoint
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Capability {
+    #[serde(rename = "type")]
+    capability_type: String,
+    interface: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<CapabilityProperties>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CapabilityProperties {
+    supported: Vec<SupportedProperty>,
+    #[serde(rename = "proactivelyReported")]
+    proactively_reported: bool,
+    retrievable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SupportedProperty {
+    name: String,
+}
+
+impl Capability {
+    /// Constructs an Alexa interface capability with the given supported
+    /// properties
+    pub fn new(interface: &str, supported: &[&str]) -> Capability {
+        let properties = if supported.is_empty() {
+            None
+        } else {
+            Some(CapabilityProperties {
+                supported: supported
+                    .iter()
+                    .map(|n| SupportedProperty {
+                        name: String::from(*n),
+                    })
+                    .collect(),
+                proactively_reported: false,
+                retrievable: false,
+            })
+        };
+        Capability {
+            capability_type: String::from("AlexaInterface"),
+            interface: String::from(interface),
+            version: String::from("3"),
+            properties,
+        }
+    }
+
+    /// Constructs the base Alexa interface capability
+    pub fn alexa() -> Capability {
+        Capability::new("Alexa", &[])
+    }
+
+    /// Constructs a PowerController capability
+    pub fn power_controller() -> Capability {
+        Capability::new("Alexa.PowerController", &["powerState"])
+    }
+
+    /// Constructs a BrightnessController capability
+    pub fn brightness_controller() -> Capability {
+        Capability::new("Alexa.BrightnessController", &["brightness"])
+    }
+
+    /// Constructs a ThermostatController capability
+    pub fn thermostat_controller() -> Capability {
+        Capability::new(
+            "Alexa.ThermostatController",
+            &["targetSetpoint", "thermostatMode"],
+        )
+    }
+
+    /// Constructs an EndpointHealth capability
+    pub fn endpoint_health() -> Capability {
+        Capability::new("Alexa.EndpointHealth", &["connectivity"])
+    }
+
+    /// sets whether the properties are proactively reported and retrievable
+    pub fn reporting(mut self, proactively_reported: bool, retrievable: bool) -> Self {
+        if let Some(ref mut p) = self.properties {
+            p.proactively_reported = proactively_reported;
+            p.retrievable = retrievable;
+        }
+        self
+    }
+}
+
+impl Response {
+    fn new(namespace: &str, name: &str, message_id: &str, payload: Value) -> Response {
+        Response {
+            event: Event {
+                header: Header {
+                    namespace: String::from(namespace),
+                    name: String::from(name),
+                    payload_version: String::from(PAYLOAD_VERSION),
+                    message_id: String::from(message_id),
+                    correlation_token: None,
+                },
+                endpoint: None,
+                payload,
+            },
+            context: None,
+        }
+    }
+
+    fn empty_payload() -> Value {
+        Value::Object(serde_json::Map::new())
+    }
+
+    fn for_request(req: &Request, name: &str, message_id: &str, payload: Value) -> Response {
+        let mut res = Response::new("Alexa", name, message_id, payload);
+        res.event.header.correlation_token = req.directive.header.correlation_token.clone();
+        res.event.endpoint = req.directive.endpoint.clone();
+        res
+    }
+
+    /// Constructs a discovery response with the given endpoints
+    pub fn discover(message_id: &str, endpoints: Vec<DiscoveryEndpoint>) -> Response {
+        let mut payload = serde_json::Map::new();
+        payload.insert(
+            String::from("endpoints"),
+            serde_json::to_value(endpoints).unwrap_or(Value::Null),
+        );
+        Response::new(
+            "Alexa.Discovery",
+            "Discover.Response",
+            message_id,
+            Value::Object(payload),
+        )
+    }
+
+    /// Constructs a successful response to a controller directive, echoing its
+    /// correlation token and endpoint
+    pub fn control(req: &Request, message_id: &str) -> Response {
+        Response::for_request(req, "Response", message_id, Response::empty_payload())
+    }
+
+    /// Constructs a state report in response to a ReportState directive
+    pub fn state_report(req: &Request, message_id: &str) -> Response {
+        Response::for_request(req, "StateReport", message_id, Response::empty_payload())
+    }
+
+    /// Constructs an error response to the given directive
+    pub fn error(req: &Request, message_id: &str, error: ErrorType, message: &str) -> Response {
+        let mut payload = serde_json::Map::new();
+        payload.insert(String::from("type"), Value::from(error.to_string()));
+        payload.insert(String::from("message"), Value::from(message));
+        Response::for_request(req, "ErrorResponse", message_id, Value::Object(payload))
+    }
+
+    /// Constructs a proactive change report for the given endpoint
+    /// changed properties go in the payload, unchanged ones may be added with
+    /// `property`
+    pub fn change_report(
+        endpoint: Endpoint,
+        message_id: &str,
+        cause: ChangeCause,
+        changed: Vec<Property>,
+    ) -> Response {
+        let payload = serde_json::json!({
+            "change": {
+                "cause": { "type": cause.to_string() },
+                "properties": changed,
+            }
+        });
+        let mut res = Response::new("Alexa", "ChangeReport", message_id, payload);
+        res.event.endpoint = Some(endpoint);
+        res
+    }
+
+    /// adds a property to the response context
+    pub fn property(mut self, property: Property) -> Self {
+        self.context
+            .get_or_insert_with(|| Context {
+                properties: Vec::new(),
+            })
+            .properties
+            .push(property);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover() {
+        let req: Request = serde_json::from_str(discover_req()).unwrap();
+        assert_eq!(req.directive_type(), DirectiveType::Discover);
+        assert_eq!(req.bearer_token(), Some("access-token-from-skill"));
+        assert_eq!(req.endpoint_id(), None);
+    }
+
+    #[test]
+    fn test_discover_response() {
+        let ep = DiscoveryEndpoint::new("lamp-1", "Acme", "Lamp", "Acme smart lamp")
+            .display_category("LIGHT")
+            .capability(Capability::power_controller().reporting(true, true))
+            .capability(Capability::brightness_controller());
+        let res = Response::discover("msg-1", vec![ep]);
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["event"]["header"]["name"], "Discover.Response");
+        assert_eq!(
+            v["event"]["payload"]["endpoints"][0]["endpointId"],
+            "lamp-1"
+        );
+        let caps = &v["event"]["payload"]["endpoints"][0]["capabilities"];
+        assert_eq!(caps[0]["interface"], "Alexa");
+        assert_eq!(caps[1]["properties"]["supported"][0]["name"], "powerState");
+        assert_eq!(caps[1]["properties"]["retrievable"], true);
+        assert!(v.get("context").is_none());
+    }
+
+    #[test]
+    fn test_power_controller() {
+        let req: Request = serde_json::from_str(turn_on_req()).unwrap();
+        assert_eq!(req.directive_type(), DirectiveType::TurnOn);
+        assert_eq!(req.endpoint_id(), Some("lamp-1"));
+        assert_eq!(req.correlation_token(), Some("corr-token"));
+
+        let res = Response::control(&req, "msg-2")
+            .property(Property::power_state(true, "2019-02-03T16:20:50.52Z").uncertainty(500));
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["event"]["header"]["namespace"], "Alexa");
+        assert_eq!(v["event"]["header"]["name"], "Response");
+        assert_eq!(v["event"]["header"]["correlationToken"], "corr-token");
+        assert_eq!(v["event"]["endpoint"]["endpointId"], "lamp-1");
+        assert_eq!(v["context"]["properties"][0]["value"], "ON");
+        assert_eq!(
+            v["context"]["properties"][0]["uncertaintyInMilliseconds"],
+            500
+        );
+    }
+
+    #[test]
+    fn test_brightness_and_thermostat() {
+        let mut req: Request = serde_json::from_str(turn_on_req()).unwrap();
+        req.directive.header.namespace = String::from("Alexa.BrightnessController");
+        req.directive.header.name = String::from("AdjustBrightness");
+        req.directive.payload = serde_json::json!({ "brightnessDelta": -25 });
+        assert_eq!(req.directive_type(), DirectiveType::AdjustBrightness(-25));
+
+        req.directive.header.namespace = String::from("Alexa.ThermostatController");
+        req.directive.header.name = String::from("SetTargetTemperature");
+        req.directive.payload =
+            serde_json::json!({ "targetSetpoint": { "value": 21.5, "scale": "CELSIUS" } });
+        assert_eq!(
+            req.directive_type(),
+            DirectiveType::SetTargetTemperature(Temperature::celsius(21.5))
+        );
+    }
+
+    #[test]
+    fn test_error_response() {
+        let req: Request = serde_json::from_str(turn_on_req()).unwrap();
+        let res = Response::error(&req, "msg-3", ErrorType::EndpointUnreachable, "offline");
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["event"]["header"]["name"], "ErrorResponse");
+        assert_eq!(v["event"]["payload"]["type"], "ENDPOINT_UNREACHABLE");
+        assert_eq!(v["event"]["payload"]["message"], "offline");
+    }
+
+    #[test]
+    fn test_change_report() {
+        let res = Response::change_report(
+            Endpoint::new("lamp-1"),
+            "msg-4",
+            ChangeCause::PhysicalInteraction,
+            vec![Property::power_state(false, "2019-02-03T16:20:50.52Z")],
+        )
+        .property(Property::brightness(80, "2019-02-03T16:20:50.52Z"));
+        let v = serde_json::to_value(&res).unwrap();
+        assert_eq!(v["event"]["header"]["name"], "ChangeReport");
+        assert_eq!(
+            v["event"]["payload"]["change"]["cause"]["type"],
+            "PHYSICAL_INTERACTION"
+        );
+        assert_eq!(
+            v["event"]["payload"]["change"]["properties"][0]["value"],
+            "OFF"
+        );
+        assert_eq!(v["context"]["properties"][0]["value"], 80);
+    }
+
+    fn discover_req() -> &'static str {
+        r#"{
+	"directive": {
+		"header": {
+			"namespace": "Alexa.Discovery",
+			"name": "Discover",
+			"payloadVersion": "3",
+			"messageId": "1bd5d003-31b9-476f-ad03-71d471922820"
+		},
+		"payload": {
+			"scope": {
+				"type": "BearerToken",
+				"token": "access-token-from-skill"
+			}
+		}
+	}
+}"#
+    }
+
+    fn turn_on_req() -> &'static str {
+        r#"{
+	"directive": {
+		"header": {
+			"namespace": "Alexa.PowerController",
+			"name": "TurnOn",
+			"payloadVersion": "3",
+			"messageId": "1bd5d003-31b9-476f-ad03-71d471922820",
+			"correlationToken": "corr-token"
+		},
+		"endpoint": {
+			"scope": {
+				"type": "BearerToken",
+				"token": "access-token-from-skill"
+			},
+			"endpointId": "lamp-1",
+			"cookie": {}
+		},
+		"payload": {}
+	}
+}"#
+    }
+}
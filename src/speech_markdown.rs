@@ -0,0 +1,313 @@
+//! Converter from [Speech Markdown](https://www.speechmarkdown.org/) to Alexa SSML
+//!
+//! Supported syntax:
+//!
+//! * breaks: `[500ms]`, `[2s]`, `[break:"strong"]`, and the
+//!   `(pause: 500ms)` form
+//! * emphasis: `++strong++`, `+moderate+`
+//! * modifiers: `(text)[modifier]`, several separated by `;`, e.g.
+//!   `(hello)[excited:"high";rate:"slow"]`
+//! * audio: `!["https://example.com/sound.mp3"]`
+//!
+//! Text is XML escaped; anything that does not parse as markdown is kept as
+//! plain text.
+
+/// Converts Speech Markdown into an SSML document suitable for `Speech::ssml`
+pub fn to_ssml(markdown: &str) -> String {
+    format!("<speak>{}</speak>", convert(markdown))
+}
+
+fn convert(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let rest = &chars[i..];
+        let parsed = match chars[i] {
+            '[' => parse_break(rest),
+            '!' => parse_audio(rest),
+            '(' => parse_modified(rest),
+            '+' if at_word_start(&chars, i) => parse_emphasis(rest),
+            _ => None,
+        };
+        match parsed {
+            Some((ssml, len)) => {
+                out.push_str(&ssml);
+                i += len;
+            }
+            None => {
+                escape_into(chars[i], &mut out);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn at_word_start(chars: &[char], i: usize) -> bool {
+    i == 0 || chars[i - 1].is_whitespace() || chars[i - 1] == '('
+}
+
+fn escape_into(c: char, out: &mut String) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        _ => out.push(c),
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        escape_into(c, &mut out);
+    }
+    out
+}
+
+/// finds `close` in `chars` starting at `from`, returning its index
+fn find(chars: &[char], from: usize, close: char) -> Option<usize> {
+    chars[from..]
+        .iter()
+        .position(|c| *c == close)
+        .map(|p| p + from)
+}
+
+fn is_duration(s: &str) -> bool {
+    let digits = s.trim_end_matches("ms").trim_end_matches('s');
+    digits != s && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn parse_break(chars: &[char]) -> Option<(String, usize)> {
+    let end = find(chars, 1, ']')?;
+    let inner: String = chars[1..end].iter().collect();
+    let inner = inner.trim();
+    if is_duration(inner) {
+        return Some((format!("<break time=\"{}\"/>", inner), end + 1));
+    }
+    let (key, value) = split_modifier(inner);
+    if key != "break" {
+        return None;
+    }
+    Some((break_ssml(&value?), end + 1))
+}
+
+/// parses `pause: 500ms` inside parentheses not followed by modifiers
+fn parse_pause(chars: &[char]) -> Option<String> {
+    let inner: String = chars.iter().collect();
+    match split_modifier(&inner) {
+        (ref key, Some(ref value)) if key == "pause" && !value.is_empty() => {
+            Some(break_ssml(value))
+        }
+        _ => None,
+    }
+}
+
+fn break_ssml(value: &str) -> String {
+    if is_duration(value) {
+        format!("<break time=\"{}\"/>", value)
+    } else {
+        format!("<break strength=\"{}\"/>", escape(value))
+    }
+}
+
+fn parse_audio(chars: &[char]) -> Option<(String, usize)> {
+    if chars.get(1) != Some(&'[') {
+        return None;
+    }
+    let end = find(chars, 2, ']')?;
+    let src: String = chars[2..end].iter().collect();
+    let src = src.trim().trim_matches('"');
+    if src.is_empty() {
+        return None;
+    }
+    Some((format!("<audio src=\"{}\"/>", escape(src)), end + 1))
+}
+
+fn parse_emphasis(chars: &[char]) -> Option<(String, usize)> {
+    let (marker, level) = if chars.get(1) == Some(&'+') {
+        (2, "strong")
+    } else {
+        (1, "moderate")
+    };
+    let mut end = marker;
+    loop {
+        end = find(chars, end, '+')?;
+        let closes = chars[end..]
+            .iter()
+            .take(marker)
+            .filter(|c| **c == '+')
+            .count()
+            == marker;
+        let boundary = chars
+            .get(end + marker)
+            .map(|c| !c.is_alphanumeric())
+            .unwrap_or(true);
+        if closes && boundary && end > marker {
+            break;
+        }
+        end += 1;
+    }
+    let text: String = chars[marker..end].iter().collect();
+    Some((
+        format!(
+            "<emphasis level=\"{}\">{}</emphasis>",
+            level,
+            convert(&text)
+        ),
+        end + marker,
+    ))
+}
+
+fn parse_modified(chars: &[char]) -> Option<(String, usize)> {
+    let close = find(chars, 1, ')')?;
+    if chars.get(close + 1) != Some(&'[') {
+        return parse_pause(&chars[1..close]).map(|ssml| (ssml, close + 1));
+    }
+    let end = find(chars, close + 2, ']')?;
+    let text: String = chars[1..close].iter().collect();
+    let modifiers: String = chars[close + 2..end].iter().collect();
+    let mut ssml = convert(&text);
+    for m in modifiers
+        .split(';')
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+    {
+        let (key, value) = split_modifier(m);
+        ssml = apply_modifier(&key, value, &ssml)?;
+    }
+    Some((ssml, end + 1))
+}
+
+/// splits `key:"value"` (or bare `key`) into its parts
+fn split_modifier(m: &str) -> (String, Option<String>) {
+    match m.find(':') {
+        Some(p) => (
+            m[..p].trim().to_lowercase(),
+            Some(String::from(m[p + 1..].trim().trim_matches('"'))),
+        ),
+        None => (m.trim().to_lowercase(), None),
+    }
+}
+
+fn say_as(interpret_as: &str, text: &str) -> String {
+    format!(
+        "<say-as interpret-as=\"{}\">{}</say-as>",
+        interpret_as, text
+    )
+}
+
+fn apply_modifier(key: &str, value: Option<String>, text: &str) -> Option<String> {
+    let value = value.map(|v| escape(&v));
+    let ssml = match key {
+        "emphasis" => format!(
+            "<emphasis level=\"{}\">{}</emphasis>",
+            value.unwrap_or_else(|| String::from("moderate")),
+            text
+        ),
+        "whisper" => format!("<amazon:effect name=\"whispered\">{}</amazon:effect>", text),
+        "excited" | "disappointed" => format!(
+            "<amazon:emotion name=\"{}\" intensity=\"{}\">{}</amazon:emotion>",
+            key,
+            value.unwrap_or_else(|| String::from("medium")),
+            text
+        ),
+        "address" | "characters" | "digits" | "fraction" | "interjection" | "ordinal"
+        | "telephone" | "unit" | "expletive" => say_as(key, text),
+        "chars" => say_as("characters", text),
+        "number" | "cardinal" => say_as("cardinal", text),
+        "phone" => say_as("telephone", text),
+        "bleep" => say_as("expletive", text),
+        "date" => format!(
+            "<say-as interpret-as=\"date\" format=\"{}\">{}</say-as>",
+            value.unwrap_or_else(|| String::from("mdy")),
+            text
+        ),
+        "time" => format!(
+            "<say-as interpret-as=\"time\" format=\"{}\">{}</say-as>",
+            value.unwrap_or_else(|| String::from("hms12")),
+            text
+        ),
+        "ipa" => format!(
+            "<phoneme alphabet=\"ipa\" ph=\"{}\">{}</phoneme>",
+            value?, text
+        ),
+        "sub" => format!("<sub alias=\"{}\">{}</sub>", value?, text),
+        "pitch" | "rate" | "volume" => {
+            format!("<prosody {}=\"{}\">{}</prosody>", key, value?, text)
+        }
+        "lang" => format!("<lang xml:lang=\"{}\">{}</lang>", value?, text),
+        "voice" => format!("<voice name=\"{}\">{}</voice>", value?, text),
+        _ => return None,
+    };
+    Some(ssml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(to_ssml("hello world"), "<speak>hello world</speak>");
+        assert_eq!(to_ssml("fish & chips"), "<speak>fish &amp; chips</speak>");
+    }
+
+    #[test]
+    fn test_breaks() {
+        assert_eq!(
+            to_ssml("wait [500ms] now [break:\"strong\"] go [2s]"),
+            "<speak>wait <break time=\"500ms\"/> now <break strength=\"strong\"/> go <break time=\"2s\"/></speak>"
+        );
+        assert_eq!(
+            to_ssml("wait (pause: 500ms) now (pause:\"strong\")"),
+            "<speak>wait <break time=\"500ms\"/> now <break strength=\"strong\"/></speak>"
+        );
+    }
+
+    #[test]
+    fn test_modifiers() {
+        assert_eq!(
+            to_ssml("(we won)[excited:\"high\"]"),
+            "<speak><amazon:emotion name=\"excited\" intensity=\"high\">we won</amazon:emotion></speak>"
+        );
+        assert_eq!(
+            to_ssml("(secret)[whisper;rate:\"slow\"]"),
+            "<speak><prosody rate=\"slow\"><amazon:effect name=\"whispered\">secret</amazon:effect></prosody></speak>"
+        );
+        assert_eq!(
+            to_ssml("call (5551234)[phone]"),
+            "<speak>call <say-as interpret-as=\"telephone\">5551234</say-as></speak>"
+        );
+        assert_eq!(
+            to_ssml("(pecan)[ipa:\"pɪˈkɑːn\"]"),
+            "<speak><phoneme alphabet=\"ipa\" ph=\"pɪˈkɑːn\">pecan</phoneme></speak>"
+        );
+    }
+
+    #[test]
+    fn test_emphasis() {
+        assert_eq!(
+            to_ssml("this is ++really++ +good+"),
+            "<speak>this is <emphasis level=\"strong\">really</emphasis> <emphasis level=\"moderate\">good</emphasis></speak>"
+        );
+        assert_eq!(to_ssml("1+1 is 2"), "<speak>1+1 is 2</speak>");
+    }
+
+    #[test]
+    fn test_audio() {
+        assert_eq!(
+            to_ssml("![\"https://example.com/a.mp3\"] done"),
+            "<speak><audio src=\"https://example.com/a.mp3\"/> done</speak>"
+        );
+    }
+
+    #[test]
+    fn test_unparsed_is_literal() {
+        assert_eq!(
+            to_ssml("(aside) and [not a break] (x)[unknown]"),
+            "<speak>(aside) and [not a break] (x)[unknown]</speak>"
+        );
+    }
+}
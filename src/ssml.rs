@@ -0,0 +1,282 @@
+use super::request::Locale;
+use std::fmt;
+
+/// Amazon Polly voices supported by Alexa's `<voice>` tag, see the
+/// [SSML reference](https://developer.amazon.com/docs/custom-skills/speech-synthesis-markup-language-ssml-reference.html#voice)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Voice {
+    // English (US)
+    Ivy,
+    Joanna,
+    Joey,
+    Justin,
+    Kendra,
+    Kimberly,
+    Matthew,
+    Salli,
+    // English (AU)
+    Nicole,
+    Russell,
+    // English (GB)
+    Amy,
+    Brian,
+    Emma,
+    // English (IN) and Hindi
+    Aditi,
+    Raveena,
+    // German
+    Hans,
+    Marlene,
+    Vicki,
+    // Spanish (ES)
+    Conchita,
+    Enrique,
+    Lucia,
+    // Spanish (MX)
+    Mia,
+    // Spanish (US)
+    Lupe,
+    Miguel,
+    Penelope,
+    // French (FR)
+    Celine,
+    Lea,
+    Mathieu,
+    // French (CA)
+    Chantal,
+    // Italian
+    Bianca,
+    Carla,
+    Giorgio,
+    // Japanese
+    Mizuki,
+    Takumi,
+    // Portuguese (BR)
+    Camila,
+    Ricardo,
+    Vitoria,
+}
+
+impl fmt::Display for Voice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Voice {
+    /// the locale the voice was designed for
+    pub fn locale(&self) -> Locale {
+        match *self {
+            Voice::Ivy
+            | Voice::Joanna
+            | Voice::Joey
+            | Voice::Justin
+            | Voice::Kendra
+            | Voice::Kimberly
+            | Voice::Matthew
+            | Voice::Salli => Locale::AmericanEnglish,
+            Voice::Nicole | Voice::Russell => Locale::AustralianEnglish,
+            Voice::Amy | Voice::Brian | Voice::Emma => Locale::BritishEnglish,
+            Voice::Aditi | Voice::Raveena => Locale::IndianEnglish,
+            Voice::Hans | Voice::Marlene | Voice::Vicki => Locale::German,
+            Voice::Conchita | Voice::Enrique | Voice::Lucia => Locale::Spanish,
+            Voice::Mia => Locale::MexicanSpanish,
+            Voice::Lupe | Voice::Miguel | Voice::Penelope => Locale::AmericanSpanish,
+            Voice::Celine | Voice::Lea | Voice::Mathieu => Locale::French,
+            Voice::Chantal => Locale::CanadianFrench,
+            Voice::Bianca | Voice::Carla | Voice::Giorgio => Locale::Italian,
+            Voice::Mizuki | Voice::Takumi => Locale::Japanese,
+            Voice::Camila | Voice::Ricardo | Voice::Vitoria => Locale::BrazilianPortuguese,
+        }
+    }
+
+    /// returns true if the voice may be used by a skill in the given locale,
+    /// i.e. it speaks the locale's language
+    pub fn supports(&self, locale: &Locale) -> bool {
+        if *self == Voice::Aditi && *locale == Locale::Hindi {
+            return true;
+        }
+        match locale.language() {
+            Some(l) => self.locale().language() == Some(l),
+            None => false,
+        }
+    }
+}
+
+/// wraps SSML in a `<voice>` tag
+pub fn voice(voice: Voice, ssml: &str) -> String {
+    format!("<voice name=\"{}\">{}</voice>", voice, ssml)
+}
+
+/// Speech settings applied together to a block of SSML
+#[derive(Debug, Clone, Default)]
+pub struct Style {
+    voice: Option<Voice>,
+    domain: Option<String>,
+    emotion: Option<(String, String)>,
+    whispered: bool,
+    prosody: Vec<(String, String)>,
+}
+
+impl Style {
+    /// Constructs a style leaving the speech unchanged
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// speaks with the given Polly voice
+    pub fn voice(mut self, voice: Voice) -> Self {
+        self.voice = Some(voice);
+        self
+    }
+
+    /// speaks in a speaking style (`amazon:domain`), e.g. "news" or
+    /// "conversational"
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(String::from(domain));
+        self
+    }
+
+    /// speaks with an emotion ("excited" or "disappointed") at the given
+    /// intensity ("low", "medium" or "high")
+    pub fn emotion(mut self, emotion: &str, intensity: &str) -> Self {
+        self.emotion = Some((String::from(emotion), String::from(intensity)));
+        self
+    }
+
+    /// whispers the speech
+    pub fn whispered(mut self) -> Self {
+        self.whispered = true;
+        self
+    }
+
+    /// sets the speaking rate, e.g. "slow" or "110%"
+    pub fn rate(self, rate: &str) -> Self {
+        self.prosody_attr("rate", rate)
+    }
+
+    /// sets the pitch, e.g. "high" or "+10%"
+    pub fn pitch(self, pitch: &str) -> Self {
+        self.prosody_attr("pitch", pitch)
+    }
+
+    /// sets the volume, e.g. "loud" or "-6dB"
+    pub fn volume(self, volume: &str) -> Self {
+        self.prosody_attr("volume", volume)
+    }
+
+    fn prosody_attr(mut self, name: &str, value: &str) -> Self {
+        self.prosody.retain(|(n, _)| n != name);
+        self.prosody.push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// wraps SSML in the tags for this style
+    pub fn apply(&self, ssml: &str) -> String {
+        let mut s = String::from(ssml);
+        if !self.prosody.is_empty() {
+            let attrs: Vec<String> = self
+                .prosody
+                .iter()
+                .map(|(n, v)| format!("{}=\"{}\"", n, v))
+                .collect();
+            s = format!("<prosody {}>{}</prosody>", attrs.join(" "), s);
+        }
+        if self.whispered {
+            s = format!("<amazon:effect name=\"whispered\">{}</amazon:effect>", s);
+        }
+        if let Some((emotion, intensity)) = &self.emotion {
+            s = format!(
+                "<amazon:emotion name=\"{}\" intensity=\"{}\">{}</amazon:emotion>",
+                emotion, intensity, s
+            );
+        }
+        if let Some(domain) = &self.domain {
+            s = format!("<amazon:domain name=\"{}\">{}</amazon:domain>", domain, s);
+        }
+        if let Some(v) = self.voice {
+            s = voice(v, &s);
+        }
+        s
+    }
+}
+
+/// Named speech presets, so a skill's audio persona is defined once
+#[derive(Debug, Clone)]
+pub enum Persona {
+    /// Matthew reading the news, slightly faster than usual
+    ExcitedNewscaster,
+    /// Joanna in the conversational style
+    Conversational,
+    /// Alexa, excited
+    Cheerleader,
+    /// Alexa, disappointed and a little slower
+    Commiserator,
+    /// Alexa in the long-form style, for stories and articles
+    Storyteller,
+    /// Alexa whispering quietly
+    Whisperer,
+    Custom(Style),
+}
+
+impl Persona {
+    /// the settings making up the persona
+    pub fn style(&self) -> Style {
+        match self {
+            Persona::ExcitedNewscaster => Style::new()
+                .voice(Voice::Matthew)
+                .domain("news")
+                .rate("105%"),
+            Persona::Conversational => Style::new().voice(Voice::Joanna).domain("conversational"),
+            Persona::Cheerleader => Style::new().emotion("excited", "high"),
+            Persona::Commiserator => Style::new().emotion("disappointed", "medium").rate("95%"),
+            Persona::Storyteller => Style::new().domain("long-form"),
+            Persona::Whisperer => Style::new().whispered().volume("soft"),
+            Persona::Custom(style) => style.clone(),
+        }
+    }
+
+    /// wraps SSML in the tags for this persona
+    pub fn apply(&self, ssml: &str) -> String {
+        self.style().apply(ssml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_tag() {
+        assert_eq!(
+            voice(Voice::Brian, "cheerio"),
+            "<voice name=\"Brian\">cheerio</voice>"
+        );
+    }
+
+    #[test]
+    fn test_persona() {
+        assert_eq!(
+            Persona::ExcitedNewscaster.apply("markets rallied"),
+            "<voice name=\"Matthew\"><amazon:domain name=\"news\"><prosody rate=\"105%\">markets rallied</prosody></amazon:domain></voice>"
+        );
+        assert_eq!(
+            Persona::Cheerleader.apply("you won"),
+            "<amazon:emotion name=\"excited\" intensity=\"high\">you won</amazon:emotion>"
+        );
+        let custom = Persona::Custom(Style::new().rate("slow").rate("fast").pitch("low"));
+        assert_eq!(
+            custom.apply("hi"),
+            "<prosody rate=\"fast\" pitch=\"low\">hi</prosody>"
+        );
+    }
+
+    #[test]
+    fn test_voice_supports() {
+        assert!(Voice::Brian.supports(&Locale::AmericanEnglish));
+        assert!(Voice::Mia.supports(&Locale::Spanish));
+        assert!(Voice::Aditi.supports(&Locale::Hindi));
+        assert!(!Voice::Hans.supports(&Locale::BritishEnglish));
+        assert!(!Voice::Joanna.supports(&Locale::Unknown));
+    }
+}
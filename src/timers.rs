@@ -0,0 +1,278 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+use self::serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// Timer creation payload for the [Alexa Timers API](https://developer.amazon.com/docs/smapi/alexa-timers-api-reference.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Timer {
+    duration: String,
+    #[serde(rename = "timerLabel")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timer_label: Option<String>,
+    #[serde(rename = "creationBehavior")]
+    creation_behavior: CreationBehavior,
+    #[serde(rename = "triggeringBehavior")]
+    triggering_behavior: TriggeringBehavior,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CreationBehavior {
+    #[serde(rename = "displayExperience")]
+    display_experience: DisplayExperience,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DisplayExperience {
+    visibility: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TriggeringBehavior {
+    operation: Operation,
+    #[serde(rename = "notificationConfig")]
+    notification_config: NotificationConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NotificationConfig {
+    #[serde(rename = "playAudible")]
+    play_audible: bool,
+}
+
+/// What happens when the timer elapses
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum Operation {
+    #[serde(rename = "NOTIFY_ONLY")]
+    NotifyOnly,
+    #[serde(rename = "ANNOUNCE")]
+    Announce {
+        #[serde(rename = "textToAnnounce")]
+        text_to_announce: Vec<LocalizedText>,
+    },
+    #[serde(rename = "LAUNCH_TASK")]
+    LaunchTask {
+        #[serde(rename = "textToConfirm")]
+        text_to_confirm: Vec<LocalizedText>,
+        task: TimerTask,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LocalizedText {
+    locale: String,
+    text: String,
+}
+
+impl LocalizedText {
+    pub fn new(locale: &str, text: &str) -> LocalizedText {
+        LocalizedText {
+            locale: String::from(locale),
+            text: String::from(text),
+        }
+    }
+}
+
+/// Skill task launched when a LAUNCH_TASK timer elapses
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TimerTask {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<Value>,
+}
+
+impl TimerTask {
+    /// Constructs a task reference; `name` is `<skill id>.<task name>`
+    pub fn new(name: &str, version: &str) -> TimerTask {
+        TimerTask {
+            name: String::from(name),
+            version: String::from(version),
+            input: None,
+        }
+    }
+
+    /// sets the input passed to the task when it is launched
+    pub fn input(mut self, input: Value) -> Self {
+        self.input = Some(input);
+        self
+    }
+}
+
+/// Visibility of the timer on devices with screens
+pub enum Visibility {
+    Visible,
+    Hidden,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Visibility::Visible => "VISIBLE",
+            Visibility::Hidden => "HIDDEN",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl Timer {
+    /// Constructs a visible, audible, notify-only timer for the given duration
+    pub fn new(duration: Duration) -> Timer {
+        Timer {
+            duration: iso8601_duration(duration),
+            timer_label: None,
+            creation_behavior: CreationBehavior {
+                display_experience: DisplayExperience {
+                    visibility: Visibility::Visible.to_string(),
+                },
+            },
+            triggering_behavior: TriggeringBehavior {
+                operation: Operation::NotifyOnly,
+                notification_config: NotificationConfig { play_audible: true },
+            },
+        }
+    }
+
+    /// sets the label of the timer ("pasta", "exercise")
+    pub fn label(mut self, label: &str) -> Self {
+        self.timer_label = Some(String::from(label));
+        self
+    }
+
+    /// sets whether the timer is shown on devices with screens
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.creation_behavior.display_experience.visibility = visibility.to_string();
+        self
+    }
+
+    /// sets whether the timer plays an audible alert when it elapses
+    pub fn play_audible(mut self, play_audible: bool) -> Self {
+        self.triggering_behavior.notification_config.play_audible = play_audible;
+        self
+    }
+
+    /// only notifies the user when the timer elapses
+    pub fn notify_only(mut self) -> Self {
+        self.triggering_behavior.operation = Operation::NotifyOnly;
+        self
+    }
+
+    /// announces the given text when the timer elapses
+    /// may be called once per locale
+    pub fn announce(mut self, locale: &str, text: &str) -> Self {
+        let t = LocalizedText::new(locale, text);
+        match self.triggering_behavior.operation {
+            Operation::Announce {
+                ref mut text_to_announce,
+            } => text_to_announce.push(t),
+            _ => {
+                self.triggering_behavior.operation = Operation::Announce {
+                    text_to_announce: vec![t],
+                }
+            }
+        }
+        self
+    }
+
+    /// asks the user to confirm with the given text, then launches the task
+    /// when the timer elapses
+    pub fn launch_task(mut self, locale: &str, text_to_confirm: &str, task: TimerTask) -> Self {
+        self.triggering_behavior.operation = Operation::LaunchTask {
+            text_to_confirm: vec![LocalizedText::new(locale, text_to_confirm)],
+            task,
+        };
+        self
+    }
+}
+
+fn iso8601_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    let mut out = String::from("PT");
+    if h > 0 {
+        out.push_str(&format!("{}H", h));
+    }
+    if m > 0 {
+        out.push_str(&format!("{}M", m));
+    }
+    if s > 0 || secs == 0 {
+        out.push_str(&format!("{}S", s));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(iso8601_duration(Duration::from_secs(600)), "PT10M");
+        assert_eq!(iso8601_duration(Duration::from_secs(3725)), "PT1H2M5S");
+        assert_eq!(iso8601_duration(Duration::from_secs(0)), "PT0S");
+    }
+
+    #[test]
+    fn test_notify_only() {
+        let t = Timer::new(Duration::from_secs(300)).label("pasta");
+        let v = serde_json::to_value(&t).unwrap();
+        assert_eq!(v["duration"], "PT5M");
+        assert_eq!(v["timerLabel"], "pasta");
+        assert_eq!(
+            v["creationBehavior"]["displayExperience"]["visibility"],
+            "VISIBLE"
+        );
+        assert_eq!(v["triggeringBehavior"]["operation"]["type"], "NOTIFY_ONLY");
+        assert_eq!(
+            v["triggeringBehavior"]["notificationConfig"]["playAudible"],
+            true
+        );
+    }
+
+    #[test]
+    fn test_announce() {
+        let t = Timer::new(Duration::from_secs(60))
+            .announce("en-US", "time to run")
+            .announce("de-DE", "Zeit zu laufen")
+            .visibility(Visibility::Hidden);
+        let v = serde_json::to_value(&t).unwrap();
+        let op = &v["triggeringBehavior"]["operation"];
+        assert_eq!(op["type"], "ANNOUNCE");
+        assert_eq!(op["textToAnnounce"][0]["locale"], "en-US");
+        assert_eq!(op["textToAnnounce"][1]["text"], "Zeit zu laufen");
+        assert_eq!(
+            v["creationBehavior"]["displayExperience"]["visibility"],
+            "HIDDEN"
+        );
+    }
+
+    #[test]
+    fn test_launch_task() {
+        let task = TimerTask::new("amzn1.ask.skill.myappid.StretchTask", "1")
+            .input(serde_json::json!({ "routine": "morning" }));
+        let t = Timer::new(Duration::from_secs(120)).launch_task(
+            "en-US",
+            "Timer elapsed. Would you like to launch {continueWithSkillName}?",
+            task,
+        );
+        let v = serde_json::to_value(&t).unwrap();
+        let op = &v["triggeringBehavior"]["operation"];
+        assert_eq!(op["type"], "LAUNCH_TASK");
+        assert_eq!(op["task"]["name"], "amzn1.ask.skill.myappid.StretchTask");
+        assert_eq!(op["task"]["input"]["routine"], "morning");
+        assert!(v.get("timerLabel").is_none());
+    }
+}
@@ -0,0 +1,83 @@
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
+
+use self::serde_derive::{Deserialize, Serialize};
+
+/// VideoApp.Launch directive corresponding to the [VideoApp interface spec](https://developer.amazon.com/docs/custom-skills/videoapp-interface-reference.html#launch),
+/// playing a video on the device; responses carrying it must not include
+/// shouldEndSession
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Launch {
+    #[serde(rename = "videoItem")]
+    video_item: VideoItem,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VideoItem {
+    source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<Metadata>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtitle: Option<String>,
+}
+
+impl Launch {
+    /// Constructs the directive playing the video at `source`, an HTTPS URL
+    pub fn new(source: &str) -> Launch {
+        Launch {
+            video_item: VideoItem {
+                source: String::from(source),
+                metadata: None,
+            },
+        }
+    }
+
+    /// sets the title shown while the video plays
+    pub fn title(mut self, title: &str) -> Self {
+        self.metadata().title = Some(String::from(title));
+        self
+    }
+
+    /// sets the subtitle shown while the video plays
+    pub fn subtitle(mut self, subtitle: &str) -> Self {
+        self.metadata().subtitle = Some(String::from(subtitle));
+        self
+    }
+
+    fn metadata(&mut self) -> &mut Metadata {
+        self.video_item
+            .metadata
+            .get_or_insert_with(Metadata::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch() {
+        let v = serde_json::to_value(
+            Launch::new("https://example.com/video.mp4")
+                .title("Planets")
+                .subtitle("Jupiter"),
+        )
+        .unwrap();
+        assert_eq!(v["videoItem"]["source"], "https://example.com/video.mp4");
+        assert_eq!(v["videoItem"]["metadata"]["title"], "Planets");
+        assert_eq!(v["videoItem"]["metadata"]["subtitle"], "Jupiter");
+
+        let v = serde_json::to_value(Launch::new("https://example.com/video.mp4")).unwrap();
+        assert!(v["videoItem"].get("metadata").is_none());
+    }
+}